@@ -2,7 +2,7 @@
 pub struct Error(pub &'static str);
 impl std::fmt::Display for Error{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-      f.write_str(&self.0)
+      f.write_str(self.0)
     }
 }
 impl std::error::Error for Error{}
\ No newline at end of file
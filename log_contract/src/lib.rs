@@ -1,138 +1,222 @@
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    entrypoint::{self, ProgramResult},
+    clock::Clock,
+    entrypoint::ProgramResult,
     msg,
-    program_error::ProgramError,
+    program_error::{PrintProgramError, ProgramError},
     pubkey::Pubkey,
     sysvar::Sysvar,
 };
-mod error;
-macro_rules! get_value {
-    ($src:ident,$start:expr,$end:expr) => {{
-        let mut raw = [0u8; 8];
-        raw.copy_from_slice(&(*$src)[$start..$end]);
-        u64::from_be_bytes(raw)
-    }};
-}
-macro_rules! get_from_raw_value {
-    ($src:ident,$start:expr,$end:expr) => {{
-        let mut raw = [0u8; 8];
-        raw.copy_from_slice(&$src[$start..$end]);
-        u64::from_be_bytes(raw)
-    }};
-}
-macro_rules! write_value {
-    ($src:ident,$val:ident,$start:expr,$end:expr) => {
-        (*$src)[$start..$end].copy_from_slice(&$val.to_be_bytes())
-    };
-}
-// #[cfg(testnet)]
-static tokens_program_id: Pubkey =
-    solana_program::pubkey!("9zuZUTkJBdrp6zZ6uEDjVjUHZT18rKcjchdqQYqyiy4C");
-// static SPACE_SIZE: u64 = 9;
-static BOOK_SIZE: u64 = 32 * 40;
-static LOG_DATA_SIZE: usize = 9 + 64;
+use tokens_interface::error::LogError;
+use tokens_interface::event::Event;
+use tokens_interface::log_instruction::{
+    get_log_authority, LogInstruction, BOOK_SIZE, LOG_DATA_SIZE,
+};
+use tokens_interface::log_state::CreditLog;
+use tokens_interface::TOKENS_PROGRAM_ID;
+pub mod error;
 // Declare and export the program's entrypoint
 solana_program::entrypoint!(process_instruction);
 
 // Program entrypoint's implementation
 pub fn process_instruction(
-    program_id: &Pubkey, // Public key of the account the hello world program was loaded into
-    accounts: &[AccountInfo], // The account to say hello to
-    data: &[u8],         // Ignored, all helloworld instructions are hellos
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
 ) -> ProgramResult {
+    process(program_id, accounts, data).inspect_err(|error| error.print::<LogError>())
+}
+
+fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let instruction = LogInstruction::unpack(data)?;
     let mut accounts = accounts.iter();
-    let signer = next_account_info(&mut accounts)?;
-    // let src = next_account_info(&mut accounts)?;
-    match data[0] {
-        0 => {
+    match instruction {
+        LogInstruction::InitLog { bump } => {
             //init log data account
+            let signer = next_account_info(&mut accounts)?;
             let pda = next_account_info(&mut accounts)?;
             let a1 = next_account_info(&mut accounts)?;
             let a2 = next_account_info(&mut accounts)?;
             let rent_account = next_account_info(&mut accounts)?;
-            create_account(program_id, rent_account, signer, a1, a2, pda, data[1])?;
+            create_account(program_id, rent_account, signer, a1, a2, pda, bump)?;
         }
-        1 => {
-            let bump = data[9];
+        LogInstruction::Record {
+            direction,
+            amount,
+            interest,
+            bump,
+        } => {
             //record borrow log
-            // let dst = next_account_info(&mut accounts)?;
-            let pda = next_account_info(&mut accounts)?;
-            let a1 = next_account_info(&mut accounts)?;
-            let a2 = next_account_info(&mut accounts)?;
-            let rent_account = next_account_info(&mut accounts)?;
-            // let public_account = next_account_info(&mut accounts)?;
-            let src_data = a1.key.as_ref();
-            let dst_data = a2.key.as_ref();
-            if src_data < dst_data {
-                msg!("src data bytes must greater than dst data bytes");
-                return Err(ProgramError::InvalidArgument);
-            }
-            // let line = next_account_info(&mut accounts)?;
-            let line_len = pda.data_len();
-            if line_len == 0 {
-                let rent_account = next_account_info(&mut accounts)?;
-                //create account
-                create_account(
-                    program_id,
-                    rent_account,
-                    signer,
-                    a1,
-                    a2,
-                    pda,
-                    bump,
-                )?;
-                // return Err(ProgramError::UninitializedAccount);
-            } else if line_len != LOG_DATA_SIZE {
-                msg!("log data space not is log data size 73");
-                return Err(ProgramError::InvalidAccountData);
+            let (pda, mut log) = open_log(program_id, &mut accounts, bump)?;
+            if interest > 0 {
+                if log.direction == direction {
+                    return Err(LogError::InterestFromCreditor.into());
+                }
+                log.interest = log
+                    .interest
+                    .checked_sub(interest)
+                    .ok_or(LogError::InterestExceeded)?;
             }
-            let mode = data[0];
-            let amount = get_from_raw_value!(data, 1, 9);
-            let mut line_raw = a2.data.borrow_mut();
-            let mut value = get_from_raw_value!(line_raw, 1, 9);
-            if line_raw[0] == mode {
+            if log.direction == direction {
                 //same forward
-                value += amount;
-            } else {
+                log.amount += amount;
+            } else if log.amount < amount {
                 //different forward
-                value = if value < amount {
-                    line_raw[0] = 1 - line_raw[0];
-                    amount - value
-                } else {
-                    value - amount
+                if log.interest > 0 {
+                    return Err(LogError::InterestOutstanding.into());
                 }
+                log.direction = 1 - log.direction;
+                log.amount = amount - log.amount;
+            } else {
+                log.amount -= amount;
             }
-            write_value!(line_raw, value, 1, 9);
-
-            line_raw[9..41].copy_from_slice(src_data);
-            line_raw[41..73].copy_from_slice(dst_data);
+            log.pack(&mut pda.data.borrow_mut())?;
+            log_updated(pda.key, &log);
+        }
+        LogInstruction::SetRate {
+            direction,
+            rate_bps,
+            bump,
+        } => {
+            let (pda, mut log) = open_log(program_id, &mut accounts, bump)?;
+            log.rates[direction as usize] = rate_bps;
+            log.pack(&mut pda.data.borrow_mut())?;
+            log_updated(pda.key, &log);
+        }
+        LogInstruction::SetLimit {
+            direction,
+            limit,
+            bump,
+        } => {
+            let (pda, mut log) = open_log(program_id, &mut accounts, bump)?;
+            log.limits[direction as usize] = limit;
+            log.pack(&mut pda.data.borrow_mut())?;
+            log_updated(pda.key, &log);
+        }
+        LogInstruction::SetTerms {
+            direction,
+            ratio_bps,
+            due_at,
+            bump,
+        } => {
+            let (pda, mut log) = open_log(program_id, &mut accounts, bump)?;
+            let side = direction as usize;
+            let owed = log.direction == direction && (log.amount > 0 || log.interest > 0);
+            let tighter = ratio_bps > log.ratios[side]
+                || (due_at != 0 && (log.due[side] == 0 || due_at < log.due[side]));
+            if owed && tighter {
+                return Err(LogError::TermsTightened.into());
+            }
+            log.ratios[side] = ratio_bps;
+            log.due[side] = due_at;
+            log.pack(&mut pda.data.borrow_mut())?;
+            log_updated(pda.key, &log);
         }
-        2 => {
+        LogInstruction::SetCollateral {
+            direction,
+            collateral,
+            bump,
+        } => {
+            let (pda, mut log) = open_log(program_id, &mut accounts, bump)?;
+            log.collaterals[direction as usize] = collateral;
+            log.pack(&mut pda.data.borrow_mut())?;
+            log_updated(pda.key, &log);
+        }
+        LogInstruction::InitBook => {
             //init record book
             //32 bytes/per other pubkey
+            let signer = next_account_info(&mut accounts)?;
             let book = next_account_info(&mut accounts)?;
             let rent_account = next_account_info(&mut accounts)?;
             let book_len = book.data_len();
-            if book_len == BOOK_SIZE as usize && book.owner == program_id {
+            if book_len == BOOK_SIZE && book.owner == program_id {
                 return Ok(());
-            } else if book_len != 0 || book.owner != program_id {
-                msg!("book is init or book owner is log program");
-                return Err(ProgramError::InvalidArgument);
+            } else if book_len != 0 {
+                return Err(LogError::BookOwnedElsewhere.into());
             }
             let rt = solana_program::rent::Rent::from_account_info(rent_account)?;
-            let lamports = rt.minimum_balance(BOOK_SIZE as usize);
+            let lamports = rt.minimum_balance(BOOK_SIZE);
             let ix = solana_program::system_instruction::create_account(
-                signer.key, book.key, lamports, BOOK_SIZE, program_id,
+                signer.key,
+                book.key,
+                lamports,
+                BOOK_SIZE as u64,
+                program_id,
             );
             solana_program::program::invoke(&ix, &[signer.clone(), book.clone()])?;
+            Event::InitBook { book: *book.key }.emit();
+        }
+        LogInstruction::Accrue => {
+            let pda = next_account_info(&mut accounts)?;
+            let clock = Clock::from_account_info(next_account_info(&mut accounts)?)?;
+            if pda.owner != program_id {
+                return Err(ProgramError::IllegalOwner);
+            }
+            if pda.data_len() != CreditLog::LEN {
+                msg!("log account has the old layout, nothing accrues");
+                return Ok(());
+            }
+            let mut log = CreditLog::unpack(&pda.data.borrow())?;
+            log.accrue(clock.unix_timestamp);
+            log.pack(&mut pda.data.borrow_mut())?;
+            log_updated(pda.key, &log);
         }
-        _ => return Err(ProgramError::InvalidArgument),
     }
 
     Ok(())
 }
 
+/// Check the log authority of the tokens program, create or grow the log pda
+/// and return it with the interest accrued up to now.
+fn open_log<'a, 'info>(
+    program_id: &Pubkey,
+    accounts: &mut std::slice::Iter<'a, AccountInfo<'info>>,
+    bump: u8,
+) -> Result<(&'a AccountInfo<'info>, CreditLog), ProgramError> {
+    let signer = next_account_info(accounts)?;
+    let pda = next_account_info(accounts)?;
+    let a1 = next_account_info(accounts)?;
+    let a2 = next_account_info(accounts)?;
+    let rent_account = next_account_info(accounts)?;
+    let _system_program = next_account_info(accounts)?;
+    let clock = Clock::from_account_info(next_account_info(accounts)?)?;
+    let authority = next_account_info(accounts)?;
+    if !authority.is_signer || *authority.key != get_log_authority(&TOKENS_PROGRAM_ID).0 {
+        return Err(LogError::NotTokensProgram.into());
+    }
+    if a1.key.as_ref() < a2.key.as_ref() {
+        return Err(LogError::UnorderedPair.into());
+    }
+    let expected =
+        Pubkey::create_program_address(&[a1.key.as_ref(), a2.key.as_ref(), &[bump]], program_id)?;
+    if *pda.key != expected {
+        return Err(LogError::PdaMismatch.into());
+    }
+    let line_len = pda.data_len();
+    if line_len == 0 {
+        //create account
+        create_account(program_id, rent_account, signer, a1, a2, pda, bump)?;
+    } else if pda.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    } else if line_len < LOG_DATA_SIZE {
+        //grow the account written by the old layout
+        let rt = solana_program::rent::Rent::from_account_info(rent_account)?;
+        let lamports = rt
+            .minimum_balance(LOG_DATA_SIZE)
+            .saturating_sub(pda.lamports());
+        if lamports > 0 {
+            let ix = solana_program::system_instruction::transfer(signer.key, pda.key, lamports);
+            solana_program::program::invoke(&ix, &[signer.clone(), pda.clone()])?;
+        }
+        pda.realloc(LOG_DATA_SIZE, true)?;
+    }
+    let mut log = CreditLog::unpack(&pda.data.borrow())?;
+    log.a1 = *a1.key;
+    log.a2 = *a2.key;
+    log.accrue(clock.unix_timestamp);
+    Ok((pda, log))
+}
+
 fn create_account<'info>(
     program_id: &Pubkey,
     rent_account: &AccountInfo<'info>,
@@ -143,13 +227,12 @@ fn create_account<'info>(
     bump: u8,
 ) -> ProgramResult {
     if a1.key.as_ref() < a2.key.as_ref() {
-        msg!("a1 must greater than a2");
-        return Err(ProgramError::InvalidArgument);
+        return Err(LogError::UnorderedPair.into());
     }
     let rt = solana_program::rent::Rent::from_account_info(rent_account)?;
     let ix = solana_program::system_instruction::create_account(
-        &signer.key,
-        &account.key,
+        signer.key,
+        account.key,
         rt.minimum_balance(LOG_DATA_SIZE),
         LOG_DATA_SIZE as u64,
         program_id,
@@ -159,5 +242,16 @@ fn create_account<'info>(
         &[signer.clone(), account.clone()],
         &[&[a1.key.as_ref(), a2.key.as_ref(), &[bump]]],
     )?;
+    Event::InitLog { log: *account.key }.emit();
     Ok(())
 }
+
+fn log_updated(log: &Pubkey, state: &CreditLog) {
+    Event::LogUpdated {
+        log: *log,
+        direction: state.direction,
+        amount: state.amount,
+        interest: state.interest,
+    }
+    .emit();
+}
@@ -1,212 +1,1861 @@
-use std::str::FromStr;
-
-use solana_program::instruction::AccountMeta;
+use solana_program::bpf_loader_upgradeable;
+use solana_program::clock::Clock;
+use solana_program::instruction::Instruction;
 use solana_program::program::invoke_signed;
-use solana_program::sysvar::{Sysvar, SysvarId};
+use solana_program::program_error::PrintProgramError;
+use solana_program::sysvar::Sysvar;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     msg,
-    program_error::{self, INVALID_INSTRUCTION_DATA},
+    program_error::ProgramError,
     pubkey::Pubkey,
 };
-macro_rules! get_value {
-    ($src:ident,$start:expr,$end:expr) => {{
-        let mut raw = [0u8; 8];
-        raw.copy_from_slice(&(*$src)[$start..$end]);
-        u64::from_be_bytes(raw)
-    }};
-}
-macro_rules! get_from_raw_value {
-    ($src:ident,$start:expr,$end:expr) => {{
-        let mut raw = [0u8; 8];
-        raw.copy_from_slice(&$src[$start..$end]);
-        u64::from_be_bytes(raw)
-    }};
-}
-macro_rules! write_value {
-    ($src:ident,$val:ident,$start:expr,$end:expr) => {
-        (*$src)[$start..$end].copy_from_slice(&$val.to_be_bytes())
-    };
-}
-
-static log_program_id: solana_program::pubkey::Pubkey =
-    solana_program::pubkey!("11111111111111111111111111111111");
-
-static SPACE_SIZE: u64 = 80;
+use tokens_interface::error::TokenError;
+use tokens_interface::event::Event;
+use tokens_interface::instruction::TokenInstruction;
+use tokens_interface::log_state::CreditLog;
+use tokens_interface::state::{
+    Config, Htlc, LegacyTokenAccount, Metadata, Mint, Multisig, Role, Stream, TokenAccount, Vesting,
+};
+use tokens_interface::{log_instruction, LOG_PROGRAM_ID};
 // solana_program::declare_id!("AV7gAXgDrYDnbp1AvTQ2q2i39z51eLcFBcVhwGEeCyP3");
 // Declare and export the program's entrypoint
 solana_program::entrypoint!(token_program);
 // Program entrypoint's implementation
-pub fn token_program(
-    program_id: &Pubkey, // Public key of the account the hello world program was loaded into
-    accounts: &[AccountInfo], // The account to say hello to
-    data: &[u8],         // Ignored, all helloworld instructions are hellos
-) -> ProgramResult {
+pub fn token_program(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    process(program_id, accounts, data).inspect_err(|error| error.print::<TokenError>())
+}
+
+fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let instruction = TokenInstruction::unpack(data)?;
+    let (accounts, config) = if instruction.checks_pause() {
+        let (config, accounts) = accounts
+            .split_last()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        (accounts, check_not_paused(program_id, config)?)
+    } else {
+        (accounts, Config::default())
+    };
     let mut accounts = accounts.iter();
     let signer = next_account_info(&mut accounts)?;
     let pay_account = next_account_info(&mut accounts)?;
 
-    // let program_id = next_account_info(&mut accounts)?;
-    if *pay_account.owner != program_id.clone() {
-        return Err(program_error::ProgramError::IllegalOwner);
-    }
-    match data[0] {
-        0 => {
-            let rent_id =
-                solana_program::rent::Rent::from_account_info(next_account_info(&mut accounts)?)?;
-            let lamports = rent_id.minimum_balance(SPACE_SIZE as usize);
-
+    match instruction {
+        TokenInstruction::InitAccount { bump } => {
+            let mint_account = next_account_info(&mut accounts)?;
+            let rent_account = next_account_info(&mut accounts)?;
+            let _system_program = next_account_info(&mut accounts)?;
+            let owner = next_account_info(&mut accounts).map_or(signer.key, |owner| owner.key);
+            load_mint(program_id, mint_account)?;
             //init account
+            create_pda_account(
+                program_id,
+                signer,
+                pay_account,
+                rent_account,
+                TokenAccount::LEN,
+                &[owner.as_ref(), mint_account.key.as_ref(), &[bump]],
+            )?;
+            store_token_account(pay_account, &TokenAccount::new(*owner, *mint_account.key))?;
+            Event::InitAccount {
+                account: *pay_account.key,
+                owner: *owner,
+            }
+            .emit();
+        }
+        TokenInstruction::Transfer {
+            amount: transfer_amount,
+        } => {
+            let dest_account = next_account_info(&mut accounts)?;
+            let (mint_key, mint, treasury) = next_fee_accounts(program_id, &mut accounts)?;
+            if pay_account.key == dest_account.key {
+                return Err(TokenError::SameAccount.into());
+            }
+            //transfer account
+            let mut pay_state =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            let mut dest_state = load_token_account(program_id, dest_account)?;
+            check_mint(&pay_state, mint_key)?;
+            check_mint(&dest_state, mint_key)?;
+            let transferred = transfer(
+                &mut pay_state.balance,
+                &mut dest_state.balance,
+                transfer_amount,
+                mint.transfer_fee(transfer_amount),
+            )
+            .ok_or(TokenError::InsufficientFunds)?;
+            store_token_account(pay_account, &pay_state)?;
+            store_token_account(dest_account, &dest_state)?;
+            withhold_fee(program_id, treasury, &transferred)?;
+            Event::Transfer {
+                from: *pay_account.key,
+                to: *dest_account.key,
+                amount: transferred.gross,
+                fee: transferred.fee,
+            }
+            .emit();
+        }
+        TokenInstruction::MintTo { amount } => {
+            let mint_account = next_account_info(&mut accounts)?;
+            let mut mint = load_mint(program_id, mint_account)?;
+            if mint.role_authority(&config, Role::Minter) != *signer.key {
+                return Err(TokenError::NotMintAuthority.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            let supply = mint
+                .supply
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            if mint.supply_cap.is_some_and(|cap| supply > cap) {
+                return Err(TokenError::SupplyCapExceeded.into());
+            }
+            let mut state = load_token_account(program_id, pay_account)?;
+            check_mint(&state, mint_account.key)?;
+            state.balance += amount;
+            mint.supply = supply;
+            store_token_account(pay_account, &state)?;
+            mint.pack(&mut mint_account.data.borrow_mut())?;
+            Event::MintTo {
+                account: *pay_account.key,
+                amount,
+                supply,
+            }
+            .emit();
+        }
+        TokenInstruction::Freeze { amount, unlock_at } => {
+            //frozen token
+            let mut state =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            if state.balance <= amount {
+                return Err(TokenError::InsufficientFunds.into());
+            }
+            state.balance -= amount;
+            state.frozen += amount;
+            if let Some(unlock_at) = unlock_at {
+                state.unlock_at = state.unlock_at.max(unlock_at);
+            }
+            store_token_account(pay_account, &state)?;
+            Event::Freeze {
+                account: *pay_account.key,
+                amount,
+                unlock_at: state.unlock_at,
+            }
+            .emit();
+        }
+        TokenInstruction::Borrow { amount } => {
+            let dest_account = next_account_info(&mut accounts)?;
+            let log_accounts = next_log_accounts(&mut accounts)?;
+            let lender_owner = next_account_info(&mut accounts)?;
+            let (mint_key, mint, treasury) = next_fee_accounts(program_id, &mut accounts)?;
+            if pay_account.key == dest_account.key {
+                return Err(TokenError::SameAccount.into());
+            }
+            let mut borrower =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            let payer = rent_payer(signer, accounts.as_slice());
+            //the lent amount leaves the lender balance, so the lender signs too
+            let mut lender = load_owned_token_account(
+                program_id,
+                lender_owner,
+                dest_account,
+                accounts.as_slice(),
+            )?;
+            check_mint(&borrower, mint_key)?;
+            check_mint(&lender, mint_key)?;
+            if log_accounts[0].data_len() == 0 {
+                return Err(TokenError::NoCreditLine.into());
+            }
+            let clock = Clock::from_account_info(&log_accounts[3])?;
+            let mut log = load_credit_log(&log_accounts[0])?;
+            log.accrue(clock.unix_timestamp);
+            let side = log.side(dest_account.key);
+            let limit = log.limits[side];
+            let owed = log.owed(dest_account.key, pay_account.key);
+            if owed.saturating_add(amount) > limit {
+                msg!("credit limit {} is exceeded, owed {}", limit, owed);
+                return Err(TokenError::CreditLimitExceeded.into());
+            }
+            let debt = log.debt(dest_account.key, pay_account.key);
+            if !log.is_covered(side, debt.saturating_add(amount)) {
+                return Err(TokenError::Undercollateralized.into());
+            }
+            //frozen tokens already sit outside the balance, so the lender can
+            //lend up to its balance whatever the borrower holds
+            if amount > lender.balance {
+                return Err(TokenError::InsufficientCreditValue.into());
+            }
+            let transferred = transfer(
+                &mut lender.balance,
+                &mut borrower.balance,
+                amount,
+                mint.transfer_fee(amount),
+            )
+            .ok_or(TokenError::InsufficientFunds)?;
+            borrower.borrowed = borrower
+                .borrowed
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            lender.debit = lender
+                .debit
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            store_token_account(pay_account, &borrower)?;
+            store_token_account(dest_account, &lender)?;
+            withhold_fee(program_id, treasury, &transferred)?;
+            //write log
+            write_log(
+                program_id,
+                payer,
+                dest_account,
+                pay_account,
+                &log_accounts,
+                amount,
+                0,
+            )?;
+            Event::Borrow {
+                borrower: *pay_account.key,
+                lender: *dest_account.key,
+                amount,
+            }
+            .emit();
+        }
+        TokenInstruction::InitMint { supply_cap } => {
+            let rent_account = next_account_info(&mut accounts)?;
+            let _system_program = next_account_info(&mut accounts)?;
+            let authority =
+                next_account_info(&mut accounts).map_or(signer.key, |authority| authority.key);
+            let rent = solana_program::rent::Rent::from_account_info(rent_account)?;
             let ix = solana_program::system_instruction::create_account(
                 signer.key,
                 pay_account.key,
-                lamports,
-                SPACE_SIZE,
+                rent.minimum_balance(Mint::LEN),
+                Mint::LEN as u64,
                 program_id,
             );
-
-            solana_program::program::invoke_signed(
+            solana_program::program::invoke(&ix, &[signer.clone(), pay_account.clone()])?;
+            let mint = Mint {
+                authority: *authority,
+                supply: 0,
+                supply_cap,
+                ..Default::default()
+            };
+            mint.pack(&mut pay_account.data.borrow_mut())?;
+            Event::InitMint {
+                mint: *pay_account.key,
+                authority: *authority,
+            }
+            .emit();
+        }
+        TokenInstruction::Burn { amount } => {
+            let mint_account = next_account_info(&mut accounts)?;
+            let mut state =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            let mut mint = load_mint(program_id, mint_account)?;
+            check_mint(&state, mint_account.key)?;
+            if state.balance < amount {
+                return Err(TokenError::InsufficientFunds.into());
+            }
+            state.balance -= amount;
+            mint.supply = mint
+                .supply
+                .checked_sub(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            store_token_account(pay_account, &state)?;
+            mint.pack(&mut mint_account.data.borrow_mut())?;
+            Event::Burn {
+                account: *pay_account.key,
+                amount,
+                supply: mint.supply,
+            }
+            .emit();
+        }
+        TokenInstruction::Thaw { amount } => {
+            let clock = Clock::from_account_info(next_account_info(&mut accounts)?)?;
+            let mint_account = next_account_info(&mut accounts)?;
+            let mut state = load_token_account(program_id, pay_account)?;
+            check_mint(&state, mint_account.key)?;
+            if state.owner == *signer.key {
+                if clock.unix_timestamp < state.unlock_at {
+                    msg!("frozen balance is locked until {}", state.unlock_at);
+                    return Err(TokenError::FrozenLocked.into());
+                }
+            } else if load_mint(program_id, mint_account)?.role_authority(&config, Role::Freezer)
+                != *signer.key
+            {
+                return Err(TokenError::NotOwnerOrAuthority.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            if state.frozen - state.collateral < amount {
+                msg!(
+                    "frozen balance is not enough, {} is collateral",
+                    state.collateral
+                );
+                return Err(TokenError::InsufficientFrozen.into());
+            }
+            state.frozen -= amount;
+            state.balance += amount;
+            if state.frozen == 0 {
+                state.unlock_at = 0;
+            }
+            store_token_account(pay_account, &state)?;
+            Event::Thaw {
+                account: *pay_account.key,
+                amount,
+            }
+            .emit();
+        }
+        TokenInstruction::Repay { amount } => {
+            let dest_account = next_account_info(&mut accounts)?;
+            let log_accounts = next_log_accounts(&mut accounts)?;
+            let (mint_key, mint, treasury) = next_fee_accounts(program_id, &mut accounts)?;
+            if pay_account.key == dest_account.key {
+                return Err(TokenError::SameAccount.into());
+            }
+            let mut debtor =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            let payer = rent_payer(signer, accounts.as_slice());
+            let mut creditor = load_token_account(program_id, dest_account)?;
+            check_mint(&debtor, mint_key)?;
+            check_mint(&creditor, mint_key)?;
+            let clock = Clock::from_account_info(&log_accounts[3])?;
+            let mut log = load_credit_log(&log_accounts[0])?;
+            log.accrue(clock.unix_timestamp);
+            //accrued interest is paid first, the rest pays the principal
+            let interest = if log.creditor() == *dest_account.key {
+                amount.min(log.interest)
+            } else {
+                0
+            };
+            let principal = amount - interest;
+            let owed = log.owed(dest_account.key, pay_account.key);
+            if principal > owed || principal > debtor.borrowed || principal > creditor.debit {
+                msg!(
+                    "repay amount is more than owed {} and interest {}",
+                    owed,
+                    interest
+                );
+                return Err(TokenError::RepayExceedsDebt.into());
+            }
+            let transferred = transfer(
+                &mut debtor.balance,
+                &mut creditor.balance,
+                amount,
+                mint.transfer_fee(amount),
+            )
+            .ok_or(TokenError::InsufficientFunds)?;
+            debtor.borrowed -= principal;
+            creditor.debit -= principal;
+            store_token_account(pay_account, &debtor)?;
+            store_token_account(dest_account, &creditor)?;
+            withhold_fee(program_id, treasury, &transferred)?;
+            //write log, the opposite direction of the borrow
+            write_log(
+                program_id,
+                payer,
+                pay_account,
+                dest_account,
+                &log_accounts,
+                principal,
+                interest,
+            )?;
+            Event::Repay {
+                debtor: *pay_account.key,
+                creditor: *dest_account.key,
+                principal,
+                interest,
+            }
+            .emit();
+        }
+        TokenInstruction::SetRate { rate_bps } => {
+            let dest_account = next_account_info(&mut accounts)?;
+            let log_accounts = next_log_accounts(&mut accounts)?;
+            if pay_account.key == dest_account.key {
+                return Err(TokenError::SameAccount.into());
+            }
+            let lender =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            let payer = rent_payer(signer, accounts.as_slice());
+            check_mint(&load_token_account(program_id, dest_account)?, &lender.mint)?;
+            let ix = log_instruction::set_rate(
+                LOG_PROGRAM_ID,
+                program_id,
+                payer.key,
+                pay_account.key,
+                dest_account.key,
+                rate_bps,
+            );
+            invoke_log(
+                program_id,
                 &ix,
-                &[signer.clone(), pay_account.clone()],
-                &[&[&signer.key.as_ref(), &[data[1]]]],
+                payer,
+                pay_account,
+                dest_account,
+                &log_accounts,
             )?;
+            Event::SetRate {
+                lender: *pay_account.key,
+                borrower: *dest_account.key,
+                rate_bps,
+            }
+            .emit();
         }
-        1 => {
+        TokenInstruction::SetCreditLimit { limit } => {
             let dest_account = next_account_info(&mut accounts)?;
-            if *dest_account.owner != program_id.clone() {
-                return Err(program_error::ProgramError::IllegalOwner);
+            let log_accounts = next_log_accounts(&mut accounts)?;
+            if pay_account.key == dest_account.key {
+                return Err(TokenError::SameAccount.into());
             }
-            //transfer account
-            let mut raw = [0u8; 8];
-            raw.copy_from_slice(&data[1..9]);
-            let transfer_amount = u64::from_be_bytes(raw);
-            let mut pay_account_raw = pay_account.data.borrow_mut();
-            raw.copy_from_slice(&(*pay_account_raw)[0..8]);
-            let mut pay_balance = u64::from_be_bytes(raw);
-            let mut dest_account_raw = dest_account.data.borrow_mut();
-            raw.copy_from_slice(&(*dest_account_raw)[0..8]);
-            let mut dest_balance = u64::from_be_bytes(raw);
-            if !transfer(&mut pay_balance, &mut dest_balance, transfer_amount) {
-                return Err(program_error::ProgramError::InvalidAccountData);
-            }
-            (pay_account_raw[0..8]).copy_from_slice(&pay_balance.to_be_bytes());
-            (dest_account_raw[0..8]).copy_from_slice(&dest_balance.to_be_bytes());
-        }
-        2 => {
-            //airdrop
-            let airdrop_amount = {
-                let mut raw = [0u8; 8];
-                raw.copy_from_slice(&data[1..9]);
-                u64::from_be_bytes(raw)
+            let lender =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            let payer = rent_payer(signer, accounts.as_slice());
+            check_mint(&load_token_account(program_id, dest_account)?, &lender.mint)?;
+            let ix = log_instruction::set_limit(
+                LOG_PROGRAM_ID,
+                program_id,
+                payer.key,
+                pay_account.key,
+                dest_account.key,
+                limit,
+            );
+            invoke_log(
+                program_id,
+                &ix,
+                payer,
+                pay_account,
+                dest_account,
+                &log_accounts,
+            )?;
+            Event::SetCreditLimit {
+                lender: *pay_account.key,
+                borrower: *dest_account.key,
+                limit,
+            }
+            .emit();
+        }
+        TokenInstruction::SetTerms { ratio_bps, due_at } => {
+            let dest_account = next_account_info(&mut accounts)?;
+            let log_accounts = next_log_accounts(&mut accounts)?;
+            if pay_account.key == dest_account.key {
+                return Err(TokenError::SameAccount.into());
+            }
+            let lender =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            let payer = rent_payer(signer, accounts.as_slice());
+            check_mint(&load_token_account(program_id, dest_account)?, &lender.mint)?;
+            let ix = log_instruction::set_terms(
+                LOG_PROGRAM_ID,
+                program_id,
+                payer.key,
+                pay_account.key,
+                dest_account.key,
+                ratio_bps,
+                due_at.unwrap_or_default(),
+            );
+            invoke_log(
+                program_id,
+                &ix,
+                payer,
+                pay_account,
+                dest_account,
+                &log_accounts,
+            )?;
+            Event::SetTerms {
+                lender: *pay_account.key,
+                borrower: *dest_account.key,
+                ratio_bps,
+                due_at: due_at.unwrap_or_default(),
+            }
+            .emit();
+        }
+        TokenInstruction::LockCollateral { amount } => {
+            let dest_account = next_account_info(&mut accounts)?;
+            let log_accounts = next_log_accounts(&mut accounts)?;
+            if pay_account.key == dest_account.key {
+                return Err(TokenError::SameAccount.into());
+            }
+            let mut borrower =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            let payer = rent_payer(signer, accounts.as_slice());
+            check_mint(
+                &load_token_account(program_id, dest_account)?,
+                &borrower.mint,
+            )?;
+            let log = load_credit_log(&log_accounts[0])?;
+            if borrower.frozen - borrower.collateral < amount {
+                return Err(TokenError::InsufficientFrozen.into());
+            }
+            borrower.collateral += amount;
+            store_token_account(pay_account, &borrower)?;
+            let collateral = log.collaterals[log.side(dest_account.key)]
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            set_collateral(
+                program_id,
+                payer,
+                dest_account,
+                pay_account,
+                &log_accounts,
+                collateral,
+            )?;
+            Event::LockCollateral {
+                borrower: *pay_account.key,
+                lender: *dest_account.key,
+                amount,
+            }
+            .emit();
+        }
+        TokenInstruction::ReleaseCollateral { amount } => {
+            let dest_account = next_account_info(&mut accounts)?;
+            let log_accounts = next_log_accounts(&mut accounts)?;
+            if pay_account.key == dest_account.key {
+                return Err(TokenError::SameAccount.into());
+            }
+            let mut borrower =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            let payer = rent_payer(signer, accounts.as_slice());
+            check_mint(
+                &load_token_account(program_id, dest_account)?,
+                &borrower.mint,
+            )?;
+            let clock = Clock::from_account_info(&log_accounts[3])?;
+            let mut log = load_credit_log(&log_accounts[0])?;
+            log.accrue(clock.unix_timestamp);
+            let side = log.side(dest_account.key);
+            if log.collaterals[side] < amount || borrower.collateral < amount {
+                msg!("collateral is only {}", log.collaterals[side]);
+                return Err(TokenError::InsufficientCollateral.into());
+            }
+            log.collaterals[side] -= amount;
+            let debt = log.debt(dest_account.key, pay_account.key);
+            if debt > 0
+                && (log.is_past_due(side, clock.unix_timestamp) || !log.is_covered(side, debt))
+            {
+                msg!("collateral is still needed for the debt {}", debt);
+                return Err(TokenError::CollateralNeeded.into());
+            }
+            borrower.collateral -= amount;
+            store_token_account(pay_account, &borrower)?;
+            set_collateral(
+                program_id,
+                payer,
+                dest_account,
+                pay_account,
+                &log_accounts,
+                log.collaterals[side],
+            )?;
+            Event::ReleaseCollateral {
+                borrower: *pay_account.key,
+                lender: *dest_account.key,
+                amount,
+            }
+            .emit();
+        }
+        TokenInstruction::Liquidate => {
+            let dest_account = next_account_info(&mut accounts)?;
+            let log_accounts = next_log_accounts(&mut accounts)?;
+            let (mint_key, mint, treasury) = next_fee_accounts(program_id, &mut accounts)?;
+            if pay_account.key == dest_account.key {
+                return Err(TokenError::SameAccount.into());
+            }
+            if !signer.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            let mut borrower = load_token_account(program_id, pay_account)?;
+            let mut lender = load_token_account(program_id, dest_account)?;
+            check_mint(&borrower, mint_key)?;
+            check_mint(&lender, mint_key)?;
+            let clock = Clock::from_account_info(&log_accounts[3])?;
+            let mut log = load_credit_log(&log_accounts[0])?;
+            log.accrue(clock.unix_timestamp);
+            let side = log.side(dest_account.key);
+            let debt = log.debt(dest_account.key, pay_account.key);
+            if debt == 0 {
+                return Err(TokenError::NothingOwed.into());
+            }
+            if !log.is_past_due(side, clock.unix_timestamp) && log.is_covered(side, debt) {
+                return Err(TokenError::NotLiquidatable.into());
+            }
+            //seized collateral pays the accrued interest first, like a repay
+            let seized = log.collaterals[side].min(borrower.collateral).min(debt);
+            if seized == 0 {
+                return Err(TokenError::NoCollateral.into());
+            }
+            let interest = seized.min(log.interest);
+            let principal = seized - interest;
+            let transferred = transfer(
+                &mut borrower.frozen,
+                &mut lender.balance,
+                seized,
+                mint.transfer_fee(seized),
+            )
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+            borrower.collateral -= seized;
+            if borrower.frozen == 0 {
+                borrower.unlock_at = 0;
+            }
+            borrower.borrowed = borrower.borrowed.saturating_sub(principal);
+            lender.debit = lender.debit.saturating_sub(principal);
+            store_token_account(pay_account, &borrower)?;
+            store_token_account(dest_account, &lender)?;
+            withhold_fee(program_id, treasury, &transferred)?;
+            write_log(
+                program_id,
+                signer,
+                pay_account,
+                dest_account,
+                &log_accounts,
+                principal,
+                interest,
+            )?;
+            set_collateral(
+                program_id,
+                signer,
+                dest_account,
+                pay_account,
+                &log_accounts,
+                log.collaterals[side] - seized,
+            )?;
+            Event::Liquidate {
+                borrower: *pay_account.key,
+                lender: *dest_account.key,
+                seized,
+            }
+            .emit();
+        }
+        TokenInstruction::Approve { amount } => {
+            let delegate = next_account_info(&mut accounts)?;
+            let mut state =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            state.delegate = Some(*delegate.key);
+            state.allowance = amount;
+            store_token_account(pay_account, &state)?;
+            Event::Approve {
+                account: *pay_account.key,
+                delegate: *delegate.key,
+                amount,
+            }
+            .emit();
+        }
+        TokenInstruction::Revoke => {
+            let mut state =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            state.delegate = None;
+            state.allowance = 0;
+            store_token_account(pay_account, &state)?;
+            Event::Revoke {
+                account: *pay_account.key,
+            }
+            .emit();
+        }
+        TokenInstruction::TransferFrom { amount } => {
+            let dest_account = next_account_info(&mut accounts)?;
+            let (mint_key, mint, treasury) = next_fee_accounts(program_id, &mut accounts)?;
+            if pay_account.key == dest_account.key {
+                return Err(TokenError::SameAccount.into());
+            }
+            let mut pay_state = load_token_account(program_id, pay_account)?;
+            let mut dest_state = load_token_account(program_id, dest_account)?;
+            check_mint(&pay_state, mint_key)?;
+            check_mint(&dest_state, mint_key)?;
+            if pay_state.delegate != Some(*signer.key) {
+                return Err(TokenError::NotDelegate.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            if pay_state.allowance < amount {
+                msg!("allowance {} is not enough", pay_state.allowance);
+                return Err(TokenError::InsufficientAllowance.into());
+            }
+            let transferred = transfer(
+                &mut pay_state.balance,
+                &mut dest_state.balance,
+                amount,
+                mint.transfer_fee(amount),
+            )
+            .ok_or(TokenError::InsufficientFunds)?;
+            pay_state.allowance -= amount;
+            if pay_state.allowance == 0 {
+                pay_state.delegate = None;
+            }
+            store_token_account(pay_account, &pay_state)?;
+            store_token_account(dest_account, &dest_state)?;
+            withhold_fee(program_id, treasury, &transferred)?;
+            Event::Transfer {
+                from: *pay_account.key,
+                to: *dest_account.key,
+                amount: transferred.gross,
+                fee: transferred.fee,
+            }
+            .emit();
+        }
+        TokenInstruction::InitMultisig { m, bump } => {
+            let rent_account = next_account_info(&mut accounts)?;
+            let _system_program = next_account_info(&mut accounts)?;
+            let keys: Vec<Pubkey> = accounts.map(|account| *account.key).collect();
+            if m == 0 || m as usize > keys.len() || keys.len() > Multisig::MAX_SIGNERS {
+                msg!("multisig needs 1 <= m <= n <= {}", Multisig::MAX_SIGNERS);
+                return Err(TokenError::InvalidMultisig.into());
+            }
+            if keys
+                .iter()
+                .enumerate()
+                .any(|(i, key)| keys[..i].contains(key))
+            {
+                return Err(TokenError::DuplicateSigner.into());
+            }
+            let m_seed = [m];
+            let bump_seed = [bump];
+            let mut seeds: Vec<&[u8]> = vec![b"multisig", &m_seed];
+            seeds.extend(keys.iter().map(|key| key.as_ref()));
+            seeds.push(&bump_seed);
+            create_pda_account(
+                program_id,
+                signer,
+                pay_account,
+                rent_account,
+                Multisig::LEN,
+                &seeds,
+            )?;
+            let mut multisig = Multisig {
+                m,
+                n: keys.len() as u8,
+                ..Default::default()
             };
-            let mut pay_raw = pay_account.data.borrow_mut();
-            let mut balance = get_value!(pay_raw, 0, 8);
-            balance += airdrop_amount;
-            (*pay_raw)[0..8].copy_from_slice(&balance.to_be_bytes());
+            multisig.signers[..keys.len()].copy_from_slice(&keys);
+            multisig.pack(&mut pay_account.data.borrow_mut())?;
+            Event::InitMultisig {
+                multisig: *pay_account.key,
+                m,
+                n: multisig.n,
+            }
+            .emit();
         }
-        3 => {
-            //frozen token
-
-            let amount = get_from_raw_value!(data, 0, 8);
-            let mut pay_raw = pay_account.data.borrow_mut();
-            let mut balance = get_value!(pay_raw, 0, 8);
-            let mut frozen_balance = get_value!(pay_raw, 8, 16);
-            if balance <= amount {
-                msg!("balance is not enough");
-                return Err(program_error::ProgramError::AccountDataTooSmall);
-            }
-            balance -= amount;
-            frozen_balance += amount;
-            (*pay_raw)[0..8].copy_from_slice(&balance.to_be_bytes());
-            (*pay_raw)[8..16].copy_from_slice(&frozen_balance.to_be_bytes());
-        }
-        4 => {
-            //borrow 16~24,debit 24~32
+        TokenInstruction::CloseAccount { logs } => {
             let dest_account = next_account_info(&mut accounts)?;
-            let mut pay_raw = pay_account.data.borrow_mut();
-            let mut dest_raw = dest_account.data.borrow_mut();
-            let amount = get_from_raw_value!(data, 1, 9);
-            let mut sbrow = get_value!(pay_raw, 16, 24);
-            let dbrow = get_value!(dest_raw, 16, 24);
-            let debits = get_value!(pay_raw, 24, 32);
-            let mut debitd = get_value!(dest_raw, 24, 32);
-            let mut scash = get_value!(pay_raw, 0, 8);
-            let dcash = get_value!(dest_raw, 0, 8);
-            let sav = scash as i64 - debits as i64;
-            if sav <= 0 {
-                msg!("borrower balance is not enough");
-                return Err(program_error::ProgramError::InvalidAccountData);
-            }
-            let sav = sav as u64;
-            let dav = (dcash + dbrow - debitd) as i64;
-            if dav <= 0 {
-                msg!("dest credit value is not enough");
-                return Err(program_error::ProgramError::InvalidAccountData);
-            }
-            let dav = dav as u64;
-            if !(amount < sav && dav > amount) {
-                msg!("borrower balance is not enough");
-                return Err(program_error::ProgramError::InvalidAccountData);
-            }
-            scash -= amount;
-            sbrow += amount;
-            debitd += amount;
-
-            write_value!(pay_raw, scash, 0, 8);
-            write_value!(dest_raw, dcash, 0, 8);
-            write_value!(pay_raw, sbrow, 16, 24);
-            write_value!(dest_raw, dbrow, 16, 24);
-            write_value!(pay_raw, debits, 24, 32);
-            write_value!(dest_raw, debitd, 24, 32);
-            //write log
-            write_log(program_id, pay_account, dest_account, amount)?;
+            let log_accounts = (0..logs)
+                .map(|_| next_account_info(&mut accounts))
+                .collect::<Result<Vec<_>, _>>()?;
+            if pay_account.key == dest_account.key {
+                return Err(TokenError::SameAccount.into());
+            }
+            let state =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            if state.balance != 0 || state.frozen != 0 || state.borrowed != 0 || state.debit != 0 {
+                return Err(TokenError::AccountNotEmpty.into());
+            }
+            //interest stays in the log after the principal is repaid, and
+            //stops accruing once there is no principal
+            for log_account in log_accounts {
+                let log = load_credit_log(log_account)?;
+                if log.a1 != *pay_account.key && log.a2 != *pay_account.key {
+                    return Err(ProgramError::InvalidArgument);
+                }
+                if log.amount != 0 || log.interest != 0 {
+                    msg!("credit log {} is not settled", log_account.key);
+                    return Err(TokenError::AccountNotEmpty.into());
+                }
+            }
+            close_program_account(pay_account, dest_account)?;
+            Event::CloseAccount {
+                account: *pay_account.key,
+                destination: *dest_account.key,
+            }
+            .emit();
         }
-        _ => {
-            return Err(INVALID_INSTRUCTION_DATA.into());
+        TokenInstruction::TransferBatch { amounts } => {
+            let dest_accounts = (0..amounts.len())
+                .map(|_| next_account_info(&mut accounts))
+                .collect::<Result<Vec<_>, _>>()?;
+            let (mint_key, mint, treasury) = next_fee_accounts(program_id, &mut accounts)?;
+            let mut pay_state =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            check_mint(&pay_state, mint_key)?;
+            let total = amounts
+                .iter()
+                .try_fold(0u64, |total, amount| total.checked_add(*amount))
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            if pay_state.balance < total {
+                msg!("balance is not enough for the batch total {}", total);
+                return Err(TokenError::InsufficientFunds.into());
+            }
+            //every destination is stored before the next one is loaded, so
+            //a destination listed twice is credited twice
+            let mut batch = Transferred {
+                gross: 0,
+                fee: 0,
+                net: 0,
+            };
+            for (dest_account, amount) in dest_accounts.into_iter().zip(amounts) {
+                if pay_account.key == dest_account.key {
+                    return Err(TokenError::SameAccount.into());
+                }
+                let mut dest_state = load_token_account(program_id, dest_account)?;
+                check_mint(&dest_state, mint_key)?;
+                let transferred = transfer(
+                    &mut pay_state.balance,
+                    &mut dest_state.balance,
+                    amount,
+                    mint.transfer_fee(amount),
+                )
+                .ok_or(TokenError::InsufficientFunds)?;
+                batch.gross += transferred.gross;
+                batch.fee += transferred.fee;
+                batch.net += transferred.net;
+                store_token_account(dest_account, &dest_state)?;
+                Event::Transfer {
+                    from: *pay_account.key,
+                    to: *dest_account.key,
+                    amount: transferred.gross,
+                    fee: transferred.fee,
+                }
+                .emit();
+            }
+            store_token_account(pay_account, &pay_state)?;
+            withhold_fee(program_id, treasury, &batch)?;
         }
+        TokenInstruction::LockHtlc {
+            bump,
+            amount,
+            hash,
+            expiry_slot,
+        } => {
+            let recipient_account = next_account_info(&mut accounts)?;
+            let escrow_account = next_account_info(&mut accounts)?;
+            let rent_account = next_account_info(&mut accounts)?;
+            let _system_program = next_account_info(&mut accounts)?;
+            let clock = Clock::from_account_info(next_account_info(&mut accounts)?)?;
+            let mut state =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            let payer = rent_payer(signer, accounts.as_slice());
+            let recipient = load_token_account(program_id, recipient_account)?;
+            check_mint(&recipient, &state.mint)?;
+            if expiry_slot <= clock.slot {
+                msg!(
+                    "expiry slot {} is not after slot {}",
+                    expiry_slot,
+                    clock.slot
+                );
+                return Err(TokenError::InvalidExpiry.into());
+            }
+            if amount == 0 || state.balance < amount {
+                msg!("balance is not enough to lock {}", amount);
+                return Err(TokenError::InsufficientFunds.into());
+            }
+            create_pda_account(
+                program_id,
+                payer,
+                escrow_account,
+                rent_account,
+                Htlc::LEN,
+                &[
+                    b"htlc",
+                    signer.key.as_ref(),
+                    state.mint.as_ref(),
+                    recipient.owner.as_ref(),
+                    &hash,
+                    &[bump],
+                ],
+            )?;
+            state.balance -= amount;
+            store_token_account(pay_account, &state)?;
+            let escrow = Htlc {
+                sender: *signer.key,
+                recipient: *recipient_account.key,
+                amount,
+                hash,
+                expiry_slot,
+            };
+            escrow.pack(&mut escrow_account.data.borrow_mut())?;
+            Event::LockHtlc {
+                escrow: *escrow_account.key,
+                recipient: *recipient_account.key,
+                amount,
+                expiry_slot,
+            }
+            .emit();
+        }
+        TokenInstruction::ClaimHtlc { preimage } => {
+            let recipient_account = next_account_info(&mut accounts)?;
+            let sender = next_account_info(&mut accounts)?;
+            let clock = Clock::from_account_info(next_account_info(&mut accounts)?)?;
+            let (mint_key, mint, treasury) = next_fee_accounts(program_id, &mut accounts)?;
+            if !signer.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            let escrow = load_htlc(program_id, pay_account)?;
+            if solana_program::hash::hash(&preimage).to_bytes() != escrow.hash {
+                return Err(TokenError::HashMismatch.into());
+            }
+            if clock.slot >= escrow.expiry_slot {
+                msg!("escrow expired at slot {}", escrow.expiry_slot);
+                return Err(TokenError::EscrowExpired.into());
+            }
+            if *recipient_account.key != escrow.recipient || *sender.key != escrow.sender {
+                return Err(TokenError::AccountMismatch.into());
+            }
+            let transferred = pay_out(
+                program_id,
+                recipient_account,
+                mint_key,
+                &mint,
+                escrow.amount,
+            )?;
+            close_program_account(pay_account, sender)?;
+            withhold_fee(program_id, treasury, &transferred)?;
+            Event::ClaimHtlc {
+                escrow: *pay_account.key,
+                recipient: *recipient_account.key,
+                amount: escrow.amount,
+            }
+            .emit();
+        }
+        TokenInstruction::ReclaimHtlc => {
+            let sender_account = next_account_info(&mut accounts)?;
+            let clock = Clock::from_account_info(next_account_info(&mut accounts)?)?;
+            let recipient_account = next_account_info(&mut accounts)?;
+            let escrow = load_htlc(program_id, pay_account)?;
+            if escrow.sender != *signer.key {
+                return Err(TokenError::NotSender.into());
+            }
+            if *recipient_account.key != escrow.recipient {
+                return Err(TokenError::AccountMismatch.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            if clock.slot < escrow.expiry_slot {
+                msg!("escrow is locked until slot {}", escrow.expiry_slot);
+                return Err(TokenError::EscrowLocked.into());
+            }
+            let mut state = load_token_account(program_id, sender_account)?;
+            if state.owner != escrow.sender {
+                return Err(TokenError::WrongTokenAccount.into());
+            }
+            //the recipient may be frozen, only its mint matters here
+            check_mint(
+                &load_frozen_token_account(program_id, recipient_account)?,
+                &state.mint,
+            )?;
+            state.balance = state
+                .balance
+                .checked_add(escrow.amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            store_token_account(sender_account, &state)?;
+            close_program_account(pay_account, signer)?;
+            Event::ReclaimHtlc {
+                escrow: *pay_account.key,
+                sender: *sender_account.key,
+                amount: escrow.amount,
+            }
+            .emit();
+        }
+        TokenInstruction::CreateVesting {
+            bump,
+            total,
+            start,
+            cliff,
+            end,
+            revocable,
+        } => {
+            let beneficiary_account = next_account_info(&mut accounts)?;
+            let vesting_account = next_account_info(&mut accounts)?;
+            let rent_account = next_account_info(&mut accounts)?;
+            let _system_program = next_account_info(&mut accounts)?;
+            let mut state =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            let payer = rent_payer(signer, accounts.as_slice());
+            check_mint(
+                &load_token_account(program_id, beneficiary_account)?,
+                &state.mint,
+            )?;
+            if !(start <= cliff && cliff <= end) {
+                return Err(TokenError::InvalidSchedule.into());
+            }
+            if total == 0 || state.balance < total {
+                msg!("balance is not enough to vest {}", total);
+                return Err(TokenError::InsufficientFunds.into());
+            }
+            create_pda_account(
+                program_id,
+                payer,
+                vesting_account,
+                rent_account,
+                Vesting::LEN,
+                &[
+                    b"vesting",
+                    signer.key.as_ref(),
+                    beneficiary_account.key.as_ref(),
+                    &[bump],
+                ],
+            )?;
+            state.balance -= total;
+            store_token_account(pay_account, &state)?;
+            let vesting = Vesting {
+                grantor: *signer.key,
+                beneficiary: *beneficiary_account.key,
+                total,
+                released: 0,
+                start,
+                cliff,
+                end,
+                revocable,
+            };
+            vesting.pack(&mut vesting_account.data.borrow_mut())?;
+            Event::CreateVesting {
+                vesting: *vesting_account.key,
+                beneficiary: *beneficiary_account.key,
+                total,
+            }
+            .emit();
+        }
+        TokenInstruction::Release => {
+            let beneficiary_account = next_account_info(&mut accounts)?;
+            let grantor = next_account_info(&mut accounts)?;
+            let clock = Clock::from_account_info(next_account_info(&mut accounts)?)?;
+            let (mint_key, mint, treasury) = next_fee_accounts(program_id, &mut accounts)?;
+            if !signer.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            let mut vesting = load_vesting(program_id, pay_account)?;
+            if vesting.beneficiary != *beneficiary_account.key || vesting.grantor != *grantor.key {
+                return Err(TokenError::AccountMismatch.into());
+            }
+            let amount = vesting.vested(clock.unix_timestamp) - vesting.released;
+            if amount == 0 {
+                return Err(TokenError::NothingVested.into());
+            }
+            let transferred = pay_out(program_id, beneficiary_account, mint_key, &mint, amount)?;
+            vesting.released += amount;
+            if vesting.released == vesting.total {
+                close_program_account(pay_account, grantor)?;
+            } else {
+                vesting.pack(&mut pay_account.data.borrow_mut())?;
+            }
+            withhold_fee(program_id, treasury, &transferred)?;
+            Event::Release {
+                vesting: *pay_account.key,
+                amount,
+            }
+            .emit();
+        }
+        TokenInstruction::RevokeVesting => {
+            let beneficiary_account = next_account_info(&mut accounts)?;
+            let grantor_account = next_account_info(&mut accounts)?;
+            let clock = Clock::from_account_info(next_account_info(&mut accounts)?)?;
+            let (mint_key, mint, treasury) = next_fee_accounts(program_id, &mut accounts)?;
+            let vesting = load_vesting(program_id, pay_account)?;
+            if vesting.grantor != *signer.key {
+                return Err(TokenError::NotGrantor.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            if !vesting.revocable {
+                return Err(TokenError::NotRevocable.into());
+            }
+            if vesting.beneficiary != *beneficiary_account.key {
+                return Err(TokenError::AccountMismatch.into());
+            }
+            let grantor_state = load_token_account(program_id, grantor_account)?;
+            if grantor_state.owner != vesting.grantor {
+                return Err(TokenError::WrongTokenAccount.into());
+            }
+            check_mint(&grantor_state, mint_key)?;
+            //what vested so far still goes to the beneficiary, the rest
+            //returns to the grantor without a fee
+            let vested = vesting.vested(clock.unix_timestamp);
+            let transferred = pay_out(
+                program_id,
+                beneficiary_account,
+                mint_key,
+                &mint,
+                vested - vesting.released,
+            )?;
+            credit_token_account(program_id, grantor_account, vesting.total - vested)?;
+            close_program_account(pay_account, signer)?;
+            withhold_fee(program_id, treasury, &transferred)?;
+            Event::RevokeVesting {
+                vesting: *pay_account.key,
+                vested: vested - vesting.released,
+                returned: vesting.total - vested,
+            }
+            .emit();
+        }
+        TokenInstruction::CreateStream {
+            bump,
+            deposit,
+            rate,
+        } => {
+            let recipient_account = next_account_info(&mut accounts)?;
+            let stream_account = next_account_info(&mut accounts)?;
+            let rent_account = next_account_info(&mut accounts)?;
+            let _system_program = next_account_info(&mut accounts)?;
+            let clock = Clock::from_account_info(next_account_info(&mut accounts)?)?;
+            let mut state =
+                load_owned_token_account(program_id, signer, pay_account, accounts.as_slice())?;
+            let payer = rent_payer(signer, accounts.as_slice());
+            check_mint(
+                &load_token_account(program_id, recipient_account)?,
+                &state.mint,
+            )?;
+            if rate == 0 {
+                return Err(TokenError::ZeroRate.into());
+            }
+            if deposit == 0 || state.balance < deposit {
+                msg!("balance is not enough to deposit {}", deposit);
+                return Err(TokenError::InsufficientFunds.into());
+            }
+            create_pda_account(
+                program_id,
+                payer,
+                stream_account,
+                rent_account,
+                Stream::LEN,
+                &[
+                    b"stream",
+                    signer.key.as_ref(),
+                    recipient_account.key.as_ref(),
+                    &[bump],
+                ],
+            )?;
+            state.balance -= deposit;
+            store_token_account(pay_account, &state)?;
+            let stream = Stream {
+                sender: *signer.key,
+                recipient: *recipient_account.key,
+                deposit,
+                withdrawn: 0,
+                rate,
+                start: clock.unix_timestamp,
+            };
+            stream.pack(&mut stream_account.data.borrow_mut())?;
+            Event::CreateStream {
+                stream: *stream_account.key,
+                recipient: *recipient_account.key,
+                deposit,
+                rate,
+            }
+            .emit();
+        }
+        TokenInstruction::Withdraw => {
+            let recipient_account = next_account_info(&mut accounts)?;
+            let sender = next_account_info(&mut accounts)?;
+            let clock = Clock::from_account_info(next_account_info(&mut accounts)?)?;
+            let (mint_key, mint, treasury) = next_fee_accounts(program_id, &mut accounts)?;
+            let mut stream = load_stream(program_id, pay_account)?;
+            if stream.recipient != *recipient_account.key || stream.sender != *sender.key {
+                return Err(TokenError::AccountMismatch.into());
+            }
+            load_owned_token_account(program_id, signer, recipient_account, accounts.as_slice())?;
+            let amount = stream.available(clock.unix_timestamp);
+            if amount == 0 {
+                return Err(TokenError::NothingStreamed.into());
+            }
+            let transferred = pay_out(program_id, recipient_account, mint_key, &mint, amount)?;
+            stream.withdrawn += amount;
+            if stream.withdrawn == stream.deposit {
+                close_program_account(pay_account, sender)?;
+            } else {
+                stream.pack(&mut pay_account.data.borrow_mut())?;
+            }
+            withhold_fee(program_id, treasury, &transferred)?;
+            Event::Withdraw {
+                stream: *pay_account.key,
+                amount,
+            }
+            .emit();
+        }
+        TokenInstruction::CancelStream => {
+            let recipient_account = next_account_info(&mut accounts)?;
+            let sender_account = next_account_info(&mut accounts)?;
+            let sender = next_account_info(&mut accounts)?;
+            let clock = Clock::from_account_info(next_account_info(&mut accounts)?)?;
+            let (mint_key, mint, treasury) = next_fee_accounts(program_id, &mut accounts)?;
+            let stream = load_stream(program_id, pay_account)?;
+            if stream.recipient != *recipient_account.key || stream.sender != *sender.key {
+                return Err(TokenError::AccountMismatch.into());
+            }
+            let recipient = load_token_account(program_id, recipient_account)?;
+            if *signer.key != stream.sender && *signer.key != recipient.owner {
+                return Err(TokenError::NotStreamParty.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            let sender_state = load_token_account(program_id, sender_account)?;
+            if sender_state.owner != stream.sender {
+                return Err(TokenError::WrongTokenAccount.into());
+            }
+            check_mint(&sender_state, mint_key)?;
+            //the unstreamed rest returns to the sender without a fee
+            let (paid, refunded) = stream.cancel_split(clock.unix_timestamp);
+            let transferred = pay_out(program_id, recipient_account, mint_key, &mint, paid)?;
+            credit_token_account(program_id, sender_account, refunded)?;
+            close_program_account(pay_account, sender)?;
+            withhold_fee(program_id, treasury, &transferred)?;
+            Event::CancelStream {
+                stream: *pay_account.key,
+                paid,
+                refunded,
+            }
+            .emit();
+        }
+        TokenInstruction::SetTransferFee { fee_bps, max_fee } => {
+            let treasury = next_account_info(&mut accounts)?;
+            let mut mint = load_mint(program_id, pay_account)?;
+            if mint.role_authority(&config, Role::FeeManager) != *signer.key {
+                return Err(TokenError::NotMintAuthority.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            if fee_bps > 10_000 {
+                return Err(TokenError::FeeTooHigh.into());
+            }
+            check_mint(&load_token_account(program_id, treasury)?, pay_account.key)?;
+            mint.fee_bps = fee_bps;
+            mint.max_fee = max_fee;
+            mint.treasury = *treasury.key;
+            mint.pack(&mut pay_account.data.borrow_mut())?;
+            Event::SetTransferFee {
+                fee_bps,
+                max_fee,
+                treasury: *treasury.key,
+            }
+            .emit();
+        }
+        TokenInstruction::InitConfig { bump } => {
+            let rent_account = next_account_info(&mut accounts)?;
+            let _system_program = next_account_info(&mut accounts)?;
+            let program_data = next_account_info(&mut accounts)?;
+            let admin = next_account_info(&mut accounts).map_or(signer.key, |admin| admin.key);
+            if load_upgrade_authority(program_id, program_data)? != Some(*signer.key) {
+                return Err(TokenError::NotUpgradeAuthority.into());
+            }
+            if !signer.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            create_pda_account(
+                program_id,
+                signer,
+                pay_account,
+                rent_account,
+                Config::LEN,
+                &[b"config", &[bump]],
+            )?;
+            let config = Config {
+                admin: *admin,
+                ..Config::default()
+            };
+            config.pack(&mut pay_account.data.borrow_mut())?;
+            Event::InitConfig {
+                config: *pay_account.key,
+                admin: *admin,
+            }
+            .emit();
+        }
+        TokenInstruction::Pause | TokenInstruction::Unpause => {
+            let mut config = load_config(program_id, pay_account)?;
+            if config.authority(Role::Pauser, config.admin) != *signer.key {
+                return Err(TokenError::NotAdmin.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            config.paused = instruction == TokenInstruction::Pause;
+            config.pack(&mut pay_account.data.borrow_mut())?;
+            if config.paused {
+                Event::Pause {
+                    pauser: *signer.key,
+                }
+                .emit();
+            } else {
+                Event::Unpause {
+                    pauser: *signer.key,
+                }
+                .emit();
+            }
+        }
+        TokenInstruction::FreezeAccount { .. } | TokenInstruction::ThawAccount => {
+            let mint_account = next_account_info(&mut accounts)?;
+            let mut state = load_frozen_token_account(program_id, pay_account)?;
+            check_mint(&state, mint_account.key)?;
+            let mint = load_mint(program_id, mint_account)?;
+            if mint.role_authority(&config, Role::Freezer) != *signer.key {
+                return Err(TokenError::NotMintAuthority.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            if let TokenInstruction::FreezeAccount { reason } = instruction {
+                state.freeze_reason = Some(reason);
+                store_token_account(pay_account, &state)?;
+                Event::FreezeAccount {
+                    account: *pay_account.key,
+                    reason,
+                }
+                .emit();
+            } else {
+                if state.freeze_reason.is_none() {
+                    return Err(TokenError::AccountNotFrozen.into());
+                }
+                state.freeze_reason = None;
+                store_token_account(pay_account, &state)?;
+                Event::ThawAccount {
+                    account: *pay_account.key,
+                }
+                .emit();
+            }
+        }
+        TokenInstruction::GrantRole { role } => {
+            let holder = next_account_info(&mut accounts)?;
+            let mut config = load_config(program_id, pay_account)?;
+            if config.admin != *signer.key {
+                return Err(TokenError::NotAdmin.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            config.roles[role as usize] = *holder.key;
+            config.pack(&mut pay_account.data.borrow_mut())?;
+            Event::GrantRole {
+                role,
+                holder: *holder.key,
+            }
+            .emit();
+        }
+        TokenInstruction::RevokeRole { role } => {
+            let mut config = load_config(program_id, pay_account)?;
+            if config.admin != *signer.key {
+                return Err(TokenError::NotAdmin.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            config.roles[role as usize] = Pubkey::default();
+            config.pack(&mut pay_account.data.borrow_mut())?;
+            Event::RevokeRole { role }.emit();
+        }
+        TokenInstruction::InitMetadata {
+            bump,
+            decimals,
+            name,
+            symbol,
+            uri,
+        } => {
+            let mint_account = next_account_info(&mut accounts)?;
+            let rent_account = next_account_info(&mut accounts)?;
+            let _system_program = next_account_info(&mut accounts)?;
+            let mint = load_mint(program_id, mint_account)?;
+            if mint.authority != *signer.key {
+                return Err(TokenError::NotMintAuthority.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            let metadata = Metadata {
+                mint: *mint_account.key,
+                update_authority: mint.authority,
+                decimals,
+                name,
+                symbol,
+                uri,
+            };
+            if !metadata.fits() {
+                return Err(TokenError::InvalidMetadata.into());
+            }
+            create_pda_account(
+                program_id,
+                rent_payer(signer, accounts.as_slice()),
+                pay_account,
+                rent_account,
+                Metadata::LEN,
+                &[b"metadata", mint_account.key.as_ref(), &[bump]],
+            )?;
+            metadata.pack(&mut pay_account.data.borrow_mut())?;
+            Event::InitMetadata {
+                metadata: *pay_account.key,
+                mint: metadata.mint,
+                decimals,
+            }
+            .emit();
+        }
+        TokenInstruction::UpdateMetadata { name, symbol, uri } => {
+            let new_authority = next_account_info(&mut accounts)?;
+            let mut metadata = load_metadata(program_id, pay_account)?;
+            if metadata.update_authority != *signer.key {
+                return Err(TokenError::NotUpdateAuthority.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            metadata.update_authority = *new_authority.key;
+            metadata.name = name;
+            metadata.symbol = symbol;
+            metadata.uri = uri;
+            if !metadata.fits() {
+                return Err(TokenError::InvalidMetadata.into());
+            }
+            metadata.pack(&mut pay_account.data.borrow_mut())?;
+            Event::UpdateMetadata {
+                metadata: *pay_account.key,
+                update_authority: metadata.update_authority,
+            }
+            .emit();
+        }
+        TokenInstruction::Migrate { legacy_bump, bump } => {
+            let mint_account = next_account_info(&mut accounts)?;
+            let owner = next_account_info(&mut accounts)?;
+            let payer = next_account_info(&mut accounts)?;
+            let rent_account = next_account_info(&mut accounts)?;
+            let _system_program = next_account_info(&mut accounts)?;
+            let dest_account = next_account_info(&mut accounts)?;
+            let mut mint = load_mint(program_id, mint_account)?;
+            if mint.role_authority(&config, Role::Upgrader) != *signer.key {
+                return Err(TokenError::NotMintAuthority.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            if pay_account.owner != program_id {
+                return Err(ProgramError::IllegalOwner);
+            }
+            if pay_account.data_len() != LegacyTokenAccount::LEN {
+                return Err(TokenError::NotLegacyAccount.into());
+            }
+            let legacy_key =
+                Pubkey::create_program_address(&[owner.key.as_ref(), &[legacy_bump]], program_id)
+                    .map_err(|_| ProgramError::InvalidSeeds)?;
+            if legacy_key != *pay_account.key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            let legacy = LegacyTokenAccount::unpack(&pay_account.data.borrow())?;
+            //the credit logs are keyed on the legacy address, a debt moved
+            //away from them could never be repaid
+            if legacy.borrowed != 0 || legacy.debit != 0 {
+                return Err(TokenError::OpenCredit.into());
+            }
+            let supply = mint
+                .supply
+                .checked_add(legacy.balance)
+                .and_then(|supply| supply.checked_add(legacy.frozen))
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            if mint.supply_cap.is_some_and(|cap| supply > cap) {
+                return Err(TokenError::SupplyCapExceeded.into());
+            }
+            //move the balances into the token account of the mint
+            let seeds: &[&[u8]] = &[owner.key.as_ref(), mint_account.key.as_ref(), &[bump]];
+            let dest_key = Pubkey::create_program_address(seeds, program_id)
+                .map_err(|_| ProgramError::InvalidSeeds)?;
+            if dest_key != *dest_account.key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            let dest_state = if dest_account.data_len() == 0 {
+                create_pda_account(
+                    program_id,
+                    payer,
+                    dest_account,
+                    rent_account,
+                    TokenAccount::LEN,
+                    seeds,
+                )?;
+                legacy.migrate(*owner.key, *mint_account.key)
+            } else {
+                let mut state = load_token_account(program_id, dest_account)?;
+                let add = |value: u64, legacy: u64| {
+                    value
+                        .checked_add(legacy)
+                        .ok_or(ProgramError::ArithmeticOverflow)
+                };
+                state.balance = add(state.balance, legacy.balance)?;
+                state.frozen = add(state.frozen, legacy.frozen)?;
+                state
+            };
+            store_token_account(dest_account, &dest_state)?;
+            close_program_account(pay_account, owner)?;
+            mint.supply = supply;
+            mint.pack(&mut mint_account.data.borrow_mut())?;
+            Event::Migrate {
+                legacy: *pay_account.key,
+                account: *dest_account.key,
+                owner: *owner.key,
+            }
+            .emit();
+        }
+        TokenInstruction::SetConfigRoles { enabled } => {
+            let mut mint = load_mint(program_id, pay_account)?;
+            if mint.authority != *signer.key {
+                return Err(TokenError::NotMintAuthority.into());
+            }
+            check_signed(program_id, signer, accounts.as_slice())?;
+            mint.config_roles = enabled;
+            mint.pack(&mut pay_account.data.borrow_mut())?;
+            Event::SetConfigRoles {
+                mint: *pay_account.key,
+                enabled,
+            }
+            .emit();
+        }
+    }
+    Ok(())
+}
+
+/// Create an account owned by the program at the pda of `seeds`, paid by `payer`.
+fn create_pda_account<'info>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    rent_account: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let rent = solana_program::rent::Rent::from_account_info(rent_account)?;
+    let ix = solana_program::system_instruction::create_account(
+        payer.key,
+        account.key,
+        rent.minimum_balance(space),
+        space as u64,
+        program_id,
+    );
+    solana_program::program::invoke_signed(&ix, &[payer.clone(), account.clone()], &[seeds])
+}
+
+/// Fail when the program is paused, else return the config for the role
+/// checks. `config` must be the config pda, which is not paused and assigns
+/// no role before it is created.
+fn check_not_paused(program_id: &Pubkey, config: &AccountInfo) -> Result<Config, ProgramError> {
+    if *config.key != Pubkey::find_program_address(&[b"config"], program_id).0 {
+        msg!("last account is not the config pda");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if config.data_len() == 0 {
+        return Ok(Config::default());
+    }
+    let config = load_config(program_id, config)?;
+    if config.paused {
+        return Err(TokenError::Paused.into());
+    }
+    Ok(config)
+}
+
+fn load_config(program_id: &Pubkey, account: &AccountInfo) -> Result<Config, ProgramError> {
+    if account.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+    Config::unpack(&account.data.borrow())
+}
+
+/// Upgrade authority recorded in the program data account of the program,
+/// `None` once the program is immutable.
+fn load_upgrade_authority(
+    program_id: &Pubkey,
+    account: &AccountInfo,
+) -> Result<Option<Pubkey>, ProgramError> {
+    if *account.key != bpf_loader_upgradeable::get_program_data_address(program_id) {
+        msg!("account is not the program data of the program");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if *account.owner != bpf_loader_upgradeable::id() {
+        return Err(ProgramError::IllegalOwner);
+    }
+    //bincode layout: u32 ProgramData tag, u64 slot, option tag, authority
+    let data = account.data.borrow();
+    if data.get(..4) != Some(&[3, 0, 0, 0]) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    match data.get(12) {
+        Some(0) => Ok(None),
+        Some(1) => data
+            .get(13..45)
+            .and_then(|authority| Pubkey::try_from(authority).ok())
+            .map(Some)
+            .ok_or(ProgramError::InvalidAccountData),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+fn load_mint(program_id: &Pubkey, account: &AccountInfo) -> Result<Mint, ProgramError> {
+    if account.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+    Mint::unpack(&account.data.borrow())
+}
+
+/// Load a token account that is not frozen by the freeze authority, which
+/// keeps every path from moving funds in or out of it.
+fn load_token_account(
+    program_id: &Pubkey,
+    account: &AccountInfo,
+) -> Result<TokenAccount, ProgramError> {
+    let state = load_frozen_token_account(program_id, account)?;
+    if let Some(reason) = state.freeze_reason {
+        msg!("account is frozen, reason {}", reason);
+        return Err(TokenError::AccountFrozen.into());
+    }
+    Ok(state)
+}
+
+/// Load a token account whether or not it is frozen, for the freeze authority.
+fn load_frozen_token_account(
+    program_id: &Pubkey,
+    account: &AccountInfo,
+) -> Result<TokenAccount, ProgramError> {
+    if account.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+    TokenAccount::unpack(&account.data.borrow())
+}
+
+/// Fail unless the token account holds balance of `mint`.
+fn check_mint(state: &TokenAccount, mint: &Pubkey) -> ProgramResult {
+    if state.mint != *mint {
+        return Err(TokenError::MintMismatch.into());
+    }
+    Ok(())
+}
+
+/// Load a token account that `signer` must own and have signed for.
+fn load_owned_token_account(
+    program_id: &Pubkey,
+    signer: &AccountInfo,
+    account: &AccountInfo,
+    signers: &[AccountInfo],
+) -> Result<TokenAccount, ProgramError> {
+    let state = load_token_account(program_id, account)?;
+    if state.owner != *signer.key {
+        return Err(TokenError::NotOwner.into());
+    }
+    check_signed(program_id, signer, signers)?;
+    Ok(state)
+}
+
+/// Check that `authority` signed, or for a multisig account that at least
+/// m of its keys are among the trailing `signers`. A multisig only acts
+/// through its keys, a signature of the multisig account itself counts for
+/// nothing.
+fn check_signed(
+    program_id: &Pubkey,
+    authority: &AccountInfo,
+    signers: &[AccountInfo],
+) -> ProgramResult {
+    if authority.owner != program_id || authority.data_len() != Multisig::LEN {
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        return Ok(());
+    }
+    let multisig = Multisig::unpack(&authority.data.borrow())?;
+    let signed = multisig
+        .keys()
+        .iter()
+        .filter(|key| signers.iter().any(|s| s.is_signer && s.key == *key))
+        .count();
+    if signed < multisig.m as usize {
+        msg!("{} of {} multisig signatures", signed, multisig.m);
+        return Err(TokenError::NotEnoughSigners.into());
+    }
+    Ok(())
+}
+
+/// Account paying rent for `authority`, the first co-signer of a multisig.
+fn rent_payer<'a, 'info>(
+    authority: &'a AccountInfo<'info>,
+    signers: &'a [AccountInfo<'info>],
+) -> &'a AccountInfo<'info> {
+    if authority.is_signer {
+        authority
+    } else {
+        signers.first().unwrap_or(authority)
+    }
+}
+
+fn load_metadata(program_id: &Pubkey, account: &AccountInfo) -> Result<Metadata, ProgramError> {
+    if account.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+    Metadata::unpack(&account.data.borrow())
+}
+
+fn load_htlc(program_id: &Pubkey, account: &AccountInfo) -> Result<Htlc, ProgramError> {
+    if account.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+    Htlc::unpack(&account.data.borrow())
+}
+
+fn load_vesting(program_id: &Pubkey, account: &AccountInfo) -> Result<Vesting, ProgramError> {
+    if account.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+    Vesting::unpack(&account.data.borrow())
+}
+
+fn load_stream(program_id: &Pubkey, account: &AccountInfo) -> Result<Stream, ProgramError> {
+    if account.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
     }
-    return Ok(());
+    Stream::unpack(&account.data.borrow())
 }
-fn transfer(pay_balance: &mut u64, dest_balance: &mut u64, amount: u64) -> bool {
-    if *pay_balance < amount {
-        return false;
+
+fn load_credit_log(account: &AccountInfo) -> Result<CreditLog, ProgramError> {
+    if account.owner != &LOG_PROGRAM_ID || account.data_len() == 0 {
+        return Err(TokenError::NoCreditRecorded.into());
     }
+    CreditLog::unpack(&account.data.borrow())
+}
+
+fn store_token_account(account: &AccountInfo, state: &TokenAccount) -> ProgramResult {
+    state.pack(&mut account.data.borrow_mut())
+}
+
+/// Add `amount` to the balance of a token account.
+fn credit_token_account(program_id: &Pubkey, account: &AccountInfo, amount: u64) -> ProgramResult {
+    let mut state = load_token_account(program_id, account)?;
+    state.balance = state
+        .balance
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    store_token_account(account, &state)
+}
+
+/// Credit `amount` held by an escrow, grant or stream to a token account of
+/// the mint, less the transfer fee of the mint.
+fn pay_out(
+    program_id: &Pubkey,
+    account: &AccountInfo,
+    mint_key: &Pubkey,
+    mint: &Mint,
+    amount: u64,
+) -> Result<Transferred, ProgramError> {
+    let mut state = load_token_account(program_id, account)?;
+    check_mint(&state, mint_key)?;
+    let mut held = amount;
+    let transferred = transfer(
+        &mut held,
+        &mut state.balance,
+        amount,
+        mint.transfer_fee(amount),
+    )
+    .ok_or(ProgramError::ArithmeticOverflow)?;
+    store_token_account(account, &state)?;
+    Ok(transferred)
+}
+
+/// Move every lamport of a program owned account to `destination` and zero its data.
+fn close_program_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **destination.lamports.borrow_mut() = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **account.lamports.borrow_mut() = 0;
+    account.data.borrow_mut().fill(0);
+    Ok(())
+}
+
+/// Amounts of a transfer: `gross` leaves the payer, `fee` is withheld for
+/// the treasury and `net` reaches the destination.
+struct Transferred {
+    gross: u64,
+    fee: u64,
+    net: u64,
+}
+
+fn transfer(
+    pay_balance: &mut u64,
+    dest_balance: &mut u64,
+    amount: u64,
+    fee: u64,
+) -> Option<Transferred> {
+    if *pay_balance < amount || fee > amount {
+        return None;
+    }
+    let net = amount - fee;
+    *dest_balance = dest_balance.checked_add(net)?;
     *pay_balance -= amount;
-    *dest_balance += amount;
-    true
+    Some(Transferred {
+        gross: amount,
+        fee,
+        net,
+    })
 }
 
+/// Credit the fee of `transferred` to the treasury once the payer and the
+/// destination are stored, so either of them can be the treasury.
+fn withhold_fee(
+    program_id: &Pubkey,
+    treasury: &AccountInfo,
+    transferred: &Transferred,
+) -> ProgramResult {
+    if transferred.fee == 0 {
+        return Ok(());
+    }
+    msg!(
+        "transfer gross {} fee {} net {}",
+        transferred.gross,
+        transferred.fee,
+        transferred.net
+    );
+    credit_token_account(program_id, treasury, transferred.fee)
+}
+
+/// Mint and its treasury token account, which every path paying another
+/// owner passes after its other accounts, before any multisig co-signers.
+fn next_fee_accounts<'a, 'info>(
+    program_id: &Pubkey,
+    accounts: &mut std::slice::Iter<'a, AccountInfo<'info>>,
+) -> Result<(&'a Pubkey, Mint, &'a AccountInfo<'info>), ProgramError> {
+    let mint_account = next_account_info(accounts)?;
+    let mint = load_mint(program_id, mint_account)?;
+    let treasury = next_account_info(accounts)?;
+    if mint.fee_bps > 0 && *treasury.key != mint.treasury {
+        return Err(TokenError::TreasuryMismatch.into());
+    }
+    Ok((mint_account.key, mint, treasury))
+}
+
+/// Accounts needed to write a log pda: the log pda, rent sysvar, system program,
+/// clock sysvar, log program and log authority pda.
+fn next_log_accounts<'a, 'info>(
+    accounts: &mut std::slice::Iter<'a, AccountInfo<'info>>,
+) -> Result<Vec<AccountInfo<'info>>, ProgramError> {
+    (0..6)
+        .map(|_| next_account_info(accounts).cloned())
+        .collect()
+}
+
+/// Record `amount` of principal lent by `creditor` to `debtor` in the log program,
+/// `interest` is the accrued interest paid back by this entry.
 fn write_log<'info>(
     program_id: &Pubkey,
-    src: &AccountInfo<'info>,
-    dst: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    creditor: &AccountInfo<'info>,
+    debtor: &AccountInfo<'info>,
+    log_accounts: &[AccountInfo<'info>],
     amount: u64,
+    interest: u64,
 ) -> ProgramResult {
-    let mut data = [0u8; 10];
-    let (a1, a2) = if (src.key.as_ref() >= dst.key.as_ref()) {
-        (src.clone(), dst.clone())
-    } else {
-        data[0] = 1;
-        (dst.clone(), src.clone())
-    };
-    data[1..9].copy_from_slice(&amount.to_be_bytes());
-    let (account, bump) = solana_program::pubkey::Pubkey::find_program_address(
-        &[a1.key.as_ref(), a2.key.as_ref()],
+    let ix = log_instruction::record(
+        LOG_PROGRAM_ID,
         program_id,
+        payer.key,
+        creditor.key,
+        debtor.key,
+        amount,
+        interest,
     );
-    data[10] = bump;
-    let ix = solana_program::instruction::Instruction::new_with_bytes(
-        log_program_id,
-        &data,
-        vec![
-            AccountMeta::new(*a1.key, false),
-            AccountMeta::new(*a2.key, false),
-            AccountMeta::new(account, false),
-            AccountMeta::new_readonly(solana_program::rent::Rent::id(), false),
-        ],
+    invoke_log(program_id, &ix, payer, creditor, debtor, log_accounts)
+}
+
+/// Record the collateral `borrower` has locked for `lender` in the log program.
+fn set_collateral<'info>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'info>,
+    lender: &AccountInfo<'info>,
+    borrower: &AccountInfo<'info>,
+    log_accounts: &[AccountInfo<'info>],
+    collateral: u64,
+) -> ProgramResult {
+    let ix = log_instruction::set_collateral(
+        LOG_PROGRAM_ID,
+        program_id,
+        payer.key,
+        lender.key,
+        borrower.key,
+        collateral,
     );
+    invoke_log(program_id, &ix, payer, lender, borrower, log_accounts)
+}
 
-    invoke_signed(&ix, &[a1, a2], &[])?;
-    Ok(())
+/// Invoke the log program signed by the log authority pda.
+fn invoke_log<'info>(
+    program_id: &Pubkey,
+    ix: &Instruction,
+    payer: &AccountInfo<'info>,
+    x: &AccountInfo<'info>,
+    y: &AccountInfo<'info>,
+    log_accounts: &[AccountInfo<'info>],
+) -> ProgramResult {
+    let (_, bump) = log_instruction::get_log_authority(program_id);
+    let mut infos = vec![payer.clone(), x.clone(), y.clone()];
+    infos.extend_from_slice(log_accounts);
+    invoke_signed(ix, &infos, &[&[b"log", &[bump]]])
 }
@@ -0,0 +1,110 @@
+use solana_program::pubkey::Pubkey;
+use tokens_interface::error::TokenError;
+use tokens_interface::instruction::transfer_batch;
+
+use crate::fixtures::{Bank, PROGRAM_ID};
+
+/// A payer holding 100 of a mint charging 10% up to 5 into a treasury, and
+/// two recipients.
+fn setup() -> (Bank, Pubkey, Pubkey, Pubkey, [Pubkey; 2]) {
+    let mut bank = Bank::new();
+    let mint = bank.add_mint(&Pubkey::new_unique());
+    let from = bank.add_payer();
+    bank.add_token_account(&from, &mint, 100);
+    let treasury_owner = Pubkey::new_unique();
+    let treasury = bank.add_token_account(&treasury_owner, &mint, 0);
+    bank.set_transfer_fee(&mint, 1_000, 5, &treasury);
+    let to = [Pubkey::new_unique(), Pubkey::new_unique()];
+    for to in &to {
+        bank.add_token_account(to, &mint, 0);
+    }
+    (bank, mint, from, treasury, to)
+}
+
+#[test]
+fn batch_pays_every_recipient() {
+    let (mut bank, mint, from, treasury, to) = setup();
+    let ix = transfer_batch(
+        PROGRAM_ID,
+        &mint,
+        &from,
+        &[(to[0], 30), (to[1], 60)],
+        &treasury,
+    );
+    bank.process(&ix, &[from]).unwrap();
+    assert_eq!(bank.token_account(&from, &mint).balance, 10);
+    assert_eq!(bank.token_account(&to[0], &mint).balance, 27);
+    assert_eq!(bank.token_account(&to[1], &mint).balance, 55);
+    assert_eq!(bank.account_balance(&treasury), 8);
+}
+
+#[test]
+fn batch_credits_a_repeated_recipient_twice() {
+    let (mut bank, mint, from, treasury, to) = setup();
+    let ix = transfer_batch(
+        PROGRAM_ID,
+        &mint,
+        &from,
+        &[(to[0], 10), (to[0], 20)],
+        &treasury,
+    );
+    bank.process(&ix, &[from]).unwrap();
+    assert_eq!(bank.token_account(&to[0], &mint).balance, 27);
+}
+
+#[test]
+fn batch_over_the_balance_moves_nothing() {
+    let (mut bank, mint, from, treasury, to) = setup();
+    let ix = transfer_batch(
+        PROGRAM_ID,
+        &mint,
+        &from,
+        &[(to[0], 60), (to[1], 41)],
+        &treasury,
+    );
+    assert_eq!(
+        bank.process(&ix, &[from]),
+        Err(TokenError::InsufficientFunds.into())
+    );
+    assert_eq!(bank.token_account(&from, &mint).balance, 100);
+}
+
+#[test]
+fn batch_failing_on_a_later_recipient_moves_nothing() {
+    let (mut bank, mint, from, treasury, to) = setup();
+    //the second recipient holds another mint, after the first was credited
+    let other = bank.add_mint(&Pubkey::new_unique());
+    let account = bank.add_token_account(&to[1], &other, 0);
+    let mut ix = transfer_batch(
+        PROGRAM_ID,
+        &mint,
+        &from,
+        &[(to[0], 30), (to[1], 30)],
+        &treasury,
+    );
+    ix.accounts[3].pubkey = account;
+    assert_eq!(
+        bank.process(&ix, &[from]),
+        Err(TokenError::MintMismatch.into())
+    );
+    assert_eq!(bank.token_account(&from, &mint).balance, 100);
+    assert_eq!(bank.token_account(&to[0], &mint).balance, 0);
+    assert_eq!(bank.account_balance(&treasury), 0);
+}
+
+#[test]
+fn batch_to_the_payer_fails() {
+    let (mut bank, mint, from, treasury, to) = setup();
+    let ix = transfer_batch(
+        PROGRAM_ID,
+        &mint,
+        &from,
+        &[(to[0], 30), (from, 30)],
+        &treasury,
+    );
+    assert_eq!(
+        bank.process(&ix, &[from]),
+        Err(TokenError::SameAccount.into())
+    );
+    assert_eq!(bank.token_account(&to[0], &mint).balance, 0);
+}
@@ -0,0 +1,89 @@
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use tokens_interface::error::TokenError;
+use tokens_interface::instruction::close_account;
+use tokens_interface::LOG_PROGRAM_ID;
+
+use crate::fixtures::{Bank, PROGRAM_ID};
+
+/// An empty token account of a new owner for a new mint.
+fn setup() -> (Bank, Pubkey, Pubkey) {
+    let mut bank = Bank::new();
+    let mint = bank.add_mint(&Pubkey::new_unique());
+    let owner = bank.add_payer();
+    bank.add_token_account(&owner, &mint, 0);
+    (bank, mint, owner)
+}
+
+fn close(
+    mint: &Pubkey,
+    owner: &Pubkey,
+    counterparties: &[Pubkey],
+) -> solana_program::instruction::Instruction {
+    close_account(
+        PROGRAM_ID,
+        LOG_PROGRAM_ID,
+        mint,
+        owner,
+        owner,
+        counterparties,
+    )
+}
+
+#[test]
+fn close_settled_account() {
+    let (mut bank, mint, owner) = setup();
+    let lender = Pubkey::new_unique();
+    bank.add_credit_log(&mint, &lender, &owner, 0, 0);
+    let lamports = bank.account(&owner).lamports;
+    let ix = close(&mint, &owner, &[lender]);
+    bank.process(&ix, &[owner]).unwrap();
+    assert!(bank.account(&ix.accounts[1].pubkey).data.is_empty());
+    assert!(bank.account(&owner).lamports > lamports);
+}
+
+#[test]
+fn close_refuses_unpaid_interest() {
+    let (mut bank, mint, owner) = setup();
+    let lender = Pubkey::new_unique();
+    let borrower = Pubkey::new_unique();
+    bank.add_credit_log(&mint, &lender, &owner, 0, 3);
+    bank.add_credit_log(&mint, &owner, &borrower, 0, 0);
+    assert_eq!(
+        bank.process(&close(&mint, &owner, &[borrower, lender]), &[owner]),
+        Err(TokenError::AccountNotEmpty.into())
+    );
+}
+
+#[test]
+fn close_refuses_open_principal() {
+    let (mut bank, mint, owner) = setup();
+    let borrower = Pubkey::new_unique();
+    bank.add_credit_log(&mint, &owner, &borrower, 5, 0);
+    assert_eq!(
+        bank.process(&close(&mint, &owner, &[borrower]), &[owner]),
+        Err(TokenError::AccountNotEmpty.into())
+    );
+}
+
+#[test]
+fn close_refuses_a_log_of_other_accounts() {
+    let (mut bank, mint, owner) = setup();
+    let (x, y) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let log = bank.add_credit_log(&mint, &x, &y, 0, 0);
+    let mut ix = close(&mint, &owner, &[x]);
+    ix.accounts[3].pubkey = log;
+    assert_eq!(
+        bank.process(&ix, &[owner]),
+        Err(ProgramError::InvalidArgument)
+    );
+}
+
+#[test]
+fn close_refuses_a_balance() {
+    let (mut bank, mint, owner) = setup();
+    bank.add_token_account(&owner, &mint, 1);
+    assert_eq!(
+        bank.process(&close(&mint, &owner, &[]), &[owner]),
+        Err(TokenError::AccountNotEmpty.into())
+    );
+}
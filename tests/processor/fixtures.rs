@@ -0,0 +1,414 @@
+//! A minimal runtime for the tokens program: accounts live in a map, every
+//! instruction runs through the program entrypoint and only a successful one
+//! writes its accounts back. System program calls are carried out by the
+//! syscall stubs, any other cross program call fails.
+use std::collections::HashMap;
+use std::sync::Once;
+
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::SystemInstruction,
+    system_program,
+    sysvar::{self, SysvarId},
+};
+use tokens_interface::instruction::{
+    get_config_account, get_legacy_token_account, get_token_account, init_account_for,
+    init_multisig,
+};
+use tokens_interface::log_instruction::{get_log_account, sort_pair};
+use tokens_interface::log_state::CreditLog;
+use tokens_interface::state::{Config, LegacyTokenAccount, Mint, TokenAccount};
+use tokens_interface::{LOG_PROGRAM_ID, TOKENS_PROGRAM_ID};
+
+pub const PROGRAM_ID: Pubkey = TOKENS_PROGRAM_ID;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Account {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+}
+
+pub struct Bank {
+    accounts: HashMap<Pubkey, Account>,
+}
+
+impl Bank {
+    pub fn new() -> Self {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(Stubs));
+        });
+        let mut bank = Bank {
+            accounts: HashMap::new(),
+        };
+        bank.set_sysvar(Rent::id(), bincode::serialize(&Rent::default()).unwrap());
+        bank.set_clock(1, 1_000);
+        bank
+    }
+
+    fn set_sysvar(&mut self, key: Pubkey, data: Vec<u8>) {
+        self.set_account(
+            key,
+            Account {
+                lamports: 1,
+                data,
+                owner: sysvar::id(),
+            },
+        );
+    }
+
+    pub fn set_clock(&mut self, slot: u64, unix_timestamp: i64) {
+        let clock = Clock {
+            slot,
+            unix_timestamp,
+            ..Default::default()
+        };
+        self.set_sysvar(Clock::id(), bincode::serialize(&clock).unwrap());
+    }
+
+    pub fn set_account(&mut self, key: Pubkey, account: Account) {
+        self.accounts.insert(key, account);
+    }
+
+    pub fn account(&self, key: &Pubkey) -> Account {
+        self.accounts.get(key).cloned().unwrap_or_default()
+    }
+
+    /// A system account holding `lamports`, to pay fees and rent.
+    pub fn add_payer(&mut self) -> Pubkey {
+        let key = Pubkey::new_unique();
+        self.set_account(
+            key,
+            Account {
+                lamports: 10_000_000_000,
+                ..Default::default()
+            },
+        );
+        key
+    }
+
+    /// A mint of `authority` without a transfer fee.
+    pub fn add_mint(&mut self, authority: &Pubkey) -> Pubkey {
+        let key = Pubkey::new_unique();
+        let mut data = vec![0; Mint::LEN];
+        Mint {
+            authority: *authority,
+            ..Default::default()
+        }
+        .pack(&mut data)
+        .unwrap();
+        self.set_program_account(key, data);
+        key
+    }
+
+    pub fn mint(&self, mint: &Pubkey) -> Mint {
+        Mint::unpack(&self.account(mint).data).unwrap()
+    }
+
+    /// Charge `fee_bps` of each transfer of `mint`, at most `max_fee`, into
+    /// the token account `treasury`.
+    pub fn set_transfer_fee(
+        &mut self,
+        mint: &Pubkey,
+        fee_bps: u16,
+        max_fee: u64,
+        treasury: &Pubkey,
+    ) {
+        let mut data = self.account(mint).data;
+        Mint {
+            fee_bps,
+            max_fee,
+            treasury: *treasury,
+            ..self.mint(mint)
+        }
+        .pack(&mut data)
+        .unwrap();
+        self.set_program_account(*mint, data);
+    }
+
+    /// Config pda of `admin` without role holders.
+    pub fn add_config(&mut self, admin: &Pubkey) {
+        let mut data = vec![0; Config::LEN];
+        Config {
+            admin: *admin,
+            ..Default::default()
+        }
+        .pack(&mut data)
+        .unwrap();
+        self.set_program_account(get_config_account(&PROGRAM_ID).0, data);
+    }
+
+    pub fn config(&self) -> Config {
+        Config::unpack(&self.account(&get_config_account(&PROGRAM_ID).0).data).unwrap()
+    }
+
+    /// Token account of `owner` for `mint` holding `balance`.
+    pub fn add_token_account(&mut self, owner: &Pubkey, mint: &Pubkey, balance: u64) -> Pubkey {
+        let key = get_token_account(owner, mint, &PROGRAM_ID).0;
+        self.set_token_account(
+            &key,
+            &TokenAccount {
+                balance,
+                ..TokenAccount::new(*owner, *mint)
+            },
+        );
+        key
+    }
+
+    /// Balance of the token account at `key`, like the treasury of a mint.
+    pub fn account_balance(&self, key: &Pubkey) -> u64 {
+        TokenAccount::unpack(&self.account(key).data)
+            .unwrap()
+            .balance
+    }
+
+    pub fn token_account(&self, owner: &Pubkey, mint: &Pubkey) -> TokenAccount {
+        let key = get_token_account(owner, mint, &PROGRAM_ID).0;
+        TokenAccount::unpack(&self.account(&key).data).unwrap()
+    }
+
+    pub fn set_token_account(&mut self, key: &Pubkey, state: &TokenAccount) {
+        let mut data = vec![0; TokenAccount::LEN];
+        state.pack(&mut data).unwrap();
+        self.set_program_account(*key, data);
+    }
+
+    /// Legacy 80 byte token account of `owner`.
+    pub fn add_legacy_account(&mut self, owner: &Pubkey, legacy: &LegacyTokenAccount) -> Pubkey {
+        let key = get_legacy_token_account(owner, &PROGRAM_ID).0;
+        let mut data = vec![0; LegacyTokenAccount::LEN];
+        for (i, value) in [legacy.balance, legacy.frozen, legacy.borrowed, legacy.debit]
+            .iter()
+            .enumerate()
+        {
+            data[i * 8..i * 8 + 8].copy_from_slice(&value.to_be_bytes());
+        }
+        self.set_program_account(key, data);
+        key
+    }
+
+    /// Credit log pda between the token accounts of `x` and `y` for `mint`,
+    /// with `x` as the creditor of `amount` and `interest`.
+    pub fn add_credit_log(
+        &mut self,
+        mint: &Pubkey,
+        x: &Pubkey,
+        y: &Pubkey,
+        amount: u64,
+        interest: u64,
+    ) -> Pubkey {
+        let x = get_token_account(x, mint, &PROGRAM_ID).0;
+        let y = get_token_account(y, mint, &PROGRAM_ID).0;
+        let (a1, a2, _) = sort_pair(&x, &y);
+        let key = get_log_account(&a1, &a2, &LOG_PROGRAM_ID).0;
+        let mut data = vec![0; CreditLog::LEN];
+        CreditLog {
+            direction: if a1 == x { 0 } else { 1 },
+            amount,
+            a1,
+            a2,
+            interest,
+            ..Default::default()
+        }
+        .pack(&mut data)
+        .unwrap();
+        self.set_account(
+            key,
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: LOG_PROGRAM_ID,
+            },
+        );
+        key
+    }
+
+    pub fn set_program_account(&mut self, key: Pubkey, data: Vec<u8>) {
+        self.set_account(
+            key,
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: PROGRAM_ID,
+            },
+        );
+    }
+
+    /// Create an `m` of `keys` multisig through the program, with a token
+    /// account for `mint`.
+    pub fn add_multisig(&mut self, mint: &Pubkey, m: u8, keys: &[Pubkey]) -> Pubkey {
+        let payer = self.add_payer();
+        let ix = init_multisig(PROGRAM_ID, &payer, m, keys);
+        let multisig = ix.accounts[1].pubkey;
+        self.process(&ix, &[payer]).unwrap();
+        self.process(
+            &init_account_for(PROGRAM_ID, mint, &payer, &multisig),
+            &[payer],
+        )
+        .unwrap();
+        multisig
+    }
+
+    /// Run `ix` signed by `signers`, the signer flags of the instruction
+    /// accounts are ignored. Nothing is written back when it fails, and
+    /// accounts closed by it are removed.
+    pub fn process(&mut self, ix: &Instruction, signers: &[Pubkey]) -> ProgramResult {
+        let mut slots: Vec<Slot> = Vec::new();
+        for meta in &ix.accounts {
+            match slots.iter_mut().find(|slot| slot.key == meta.pubkey) {
+                Some(slot) => slot.writable |= meta.is_writable,
+                None => {
+                    let account = self.account(&meta.pubkey);
+                    slots.push(Slot {
+                        key: meta.pubkey,
+                        signer: signers.contains(&meta.pubkey),
+                        writable: meta.is_writable,
+                        lamports: account.lamports,
+                        data: account.data,
+                        owner: account.owner,
+                    });
+                }
+            }
+        }
+        let before: Vec<Account> = slots.iter().map(Slot::account).collect();
+        let infos: Vec<AccountInfo> = slots.iter_mut().map(Slot::info).collect();
+        let accounts: Vec<AccountInfo> = ix
+            .accounts
+            .iter()
+            .map(|meta| {
+                infos
+                    .iter()
+                    .find(|info| *info.key == meta.pubkey)
+                    .unwrap()
+                    .clone()
+            })
+            .collect();
+        tokens::token_program(&ix.program_id, &accounts, &ix.data)?;
+        let after: Vec<(Pubkey, bool, Account)> = infos
+            .iter()
+            .map(|info| {
+                (
+                    *info.key,
+                    info.is_writable,
+                    Account {
+                        lamports: info.lamports(),
+                        data: info.data.borrow().to_vec(),
+                        owner: *info.owner,
+                    },
+                )
+            })
+            .collect();
+        drop(accounts);
+        drop(infos);
+        for ((key, writable, account), before) in after.into_iter().zip(before) {
+            if !writable {
+                assert_eq!(account, before, "readonly account {} changed", key);
+            }
+            //the runtime drops the accounts left without lamports
+            if account.lamports == 0 {
+                self.accounts.remove(&key);
+            } else {
+                self.accounts.insert(key, account);
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Slot {
+    key: Pubkey,
+    signer: bool,
+    writable: bool,
+    lamports: u64,
+    data: Vec<u8>,
+    owner: Pubkey,
+}
+
+impl Slot {
+    fn account(&self) -> Account {
+        Account {
+            lamports: self.lamports,
+            data: self.data.clone(),
+            owner: self.owner,
+        }
+    }
+
+    fn info(&mut self) -> AccountInfo<'_> {
+        let Slot {
+            key,
+            signer,
+            writable,
+            lamports,
+            data,
+            owner,
+        } = self;
+        AccountInfo::new(key, *signer, *writable, lamports, data, owner, false, 0)
+    }
+}
+
+/// Carries out the system program calls of the tokens program.
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_log_data(&self, _fields: &[&[u8]]) {}
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        if instruction.program_id != system_program::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let info = |key: &Pubkey| {
+            account_infos
+                .iter()
+                .find(|info| info.key == key)
+                .ok_or(ProgramError::NotEnoughAccountKeys)
+        };
+        for meta in instruction.accounts.iter().filter(|meta| meta.is_signer) {
+            let signed = info(&meta.pubkey)?.is_signer
+                || signers_seeds.iter().any(|seeds| {
+                    Pubkey::create_program_address(seeds, &PROGRAM_ID) == Ok(meta.pubkey)
+                });
+            if !signed {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+        }
+        let from = info(&instruction.accounts[0].pubkey)?;
+        let to = info(&instruction.accounts[1].pubkey)?;
+        let lamports = match bincode::deserialize(&instruction.data).unwrap() {
+            SystemInstruction::CreateAccount {
+                lamports,
+                space,
+                owner,
+            } => {
+                if to.lamports() > 0 || !to.data_is_empty() {
+                    return Err(ProgramError::AccountAlreadyInitialized);
+                }
+                *to.data.borrow_mut() = Box::leak(vec![0; space as usize].into_boxed_slice());
+                to.assign(&owner);
+                lamports
+            }
+            SystemInstruction::Transfer { lamports } => lamports,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        **from.lamports.borrow_mut() = from
+            .lamports()
+            .checked_sub(lamports)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        **to.lamports.borrow_mut() += lamports;
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+use solana_program::{hash::hash, pubkey::Pubkey};
+use tokens_interface::error::TokenError;
+use tokens_interface::instruction::{claim_htlc, lock_htlc, reclaim_htlc};
+
+use crate::fixtures::{Bank, PROGRAM_ID};
+
+const PREIMAGE: [u8; 32] = [7; 32];
+const EXPIRY: u64 = 100;
+
+/// A sender who locked 40 of its 100 for a recipient until slot 100, on a
+/// mint charging 10% up to 5 into a treasury.
+fn setup() -> (Bank, Pubkey, Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mut bank = Bank::new();
+    let mint = bank.add_mint(&Pubkey::new_unique());
+    let sender = bank.add_payer();
+    bank.add_token_account(&sender, &mint, 100);
+    let recipient = Pubkey::new_unique();
+    bank.add_token_account(&recipient, &mint, 0);
+    let treasury = bank.add_token_account(&Pubkey::new_unique(), &mint, 0);
+    bank.set_transfer_fee(&mint, 1_000, 5, &treasury);
+    let hash = hash(&PREIMAGE).to_bytes();
+    let ix = lock_htlc(PROGRAM_ID, &mint, &sender, &recipient, 40, hash, EXPIRY);
+    let escrow = ix.accounts[3].pubkey;
+    bank.process(&ix, &[sender]).unwrap();
+    (bank, mint, sender, recipient, treasury, escrow)
+}
+
+#[test]
+fn lock_moves_the_amount_into_the_escrow() {
+    let (bank, mint, sender, ..) = setup();
+    assert_eq!(bank.token_account(&sender, &mint).balance, 60);
+}
+
+#[test]
+fn lock_needs_a_future_expiry() {
+    let (mut bank, mint, sender, recipient, ..) = setup();
+    bank.set_clock(EXPIRY, 0);
+    let ix = lock_htlc(PROGRAM_ID, &mint, &sender, &recipient, 10, [1; 32], EXPIRY);
+    assert_eq!(
+        bank.process(&ix, &[sender]),
+        Err(TokenError::InvalidExpiry.into())
+    );
+}
+
+#[test]
+fn claim_pays_the_recipient_less_the_fee() {
+    let (mut bank, mint, sender, recipient, treasury, escrow) = setup();
+    let rent = bank.account(&escrow).lamports;
+    let lamports = bank.account(&sender).lamports;
+    let caller = Pubkey::new_unique();
+    bank.set_clock(EXPIRY - 1, 0);
+    let ix = claim_htlc(
+        PROGRAM_ID, &mint, &caller, &sender, &recipient, PREIMAGE, &treasury,
+    );
+    bank.process(&ix, &[caller]).unwrap();
+    assert_eq!(bank.token_account(&recipient, &mint).balance, 36);
+    assert_eq!(bank.account_balance(&treasury), 4);
+    assert!(bank.account(&escrow).data.is_empty());
+    assert_eq!(bank.account(&sender).lamports, lamports + rent);
+}
+
+#[test]
+fn claim_with_a_wrong_preimage_fails() {
+    let (mut bank, mint, sender, recipient, treasury, escrow) = setup();
+    let caller = Pubkey::new_unique();
+    let mut ix = claim_htlc(
+        PROGRAM_ID, &mint, &caller, &sender, &recipient, [8; 32], &treasury,
+    );
+    ix.accounts[1].pubkey = escrow;
+    assert_eq!(
+        bank.process(&ix, &[caller]),
+        Err(TokenError::HashMismatch.into())
+    );
+}
+
+#[test]
+fn claim_after_the_expiry_fails() {
+    let (mut bank, mint, sender, recipient, treasury, _) = setup();
+    let caller = Pubkey::new_unique();
+    bank.set_clock(EXPIRY, 0);
+    let ix = claim_htlc(
+        PROGRAM_ID, &mint, &caller, &sender, &recipient, PREIMAGE, &treasury,
+    );
+    assert_eq!(
+        bank.process(&ix, &[caller]),
+        Err(TokenError::EscrowExpired.into())
+    );
+    assert_eq!(bank.token_account(&recipient, &mint).balance, 0);
+}
+
+#[test]
+fn reclaim_before_the_expiry_fails() {
+    let (mut bank, mint, sender, recipient, ..) = setup();
+    bank.set_clock(EXPIRY - 1, 0);
+    let ix = reclaim_htlc(
+        PROGRAM_ID,
+        &mint,
+        &sender,
+        &recipient,
+        &hash(&PREIMAGE).to_bytes(),
+    );
+    assert_eq!(
+        bank.process(&ix, &[sender]),
+        Err(TokenError::EscrowLocked.into())
+    );
+}
+
+#[test]
+fn reclaim_refunds_the_sender_without_a_fee() {
+    let (mut bank, mint, sender, recipient, treasury, escrow) = setup();
+    bank.set_clock(EXPIRY, 0);
+    let ix = reclaim_htlc(
+        PROGRAM_ID,
+        &mint,
+        &sender,
+        &recipient,
+        &hash(&PREIMAGE).to_bytes(),
+    );
+    bank.process(&ix, &[sender]).unwrap();
+    assert_eq!(bank.token_account(&sender, &mint).balance, 100);
+    assert_eq!(bank.account_balance(&treasury), 0);
+    assert!(bank.account(&escrow).data.is_empty());
+}
+
+#[test]
+fn only_the_sender_reclaims() {
+    let (mut bank, mint, sender, recipient, ..) = setup();
+    bank.set_clock(EXPIRY, 0);
+    let mut ix = reclaim_htlc(
+        PROGRAM_ID,
+        &mint,
+        &sender,
+        &recipient,
+        &hash(&PREIMAGE).to_bytes(),
+    );
+    let other = Pubkey::new_unique();
+    ix.accounts[0].pubkey = other;
+    assert_eq!(
+        bank.process(&ix, &[other]),
+        Err(TokenError::NotSender.into())
+    );
+}
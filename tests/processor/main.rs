@@ -0,0 +1,8 @@
+//! Instructions of the tokens program run against accounts held in memory.
+mod batch;
+mod close;
+mod fixtures;
+mod htlc;
+mod migrate;
+mod multisig;
+mod roles;
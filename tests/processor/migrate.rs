@@ -0,0 +1,100 @@
+use solana_program::pubkey::Pubkey;
+use tokens_interface::error::TokenError;
+use tokens_interface::instruction::migrate;
+use tokens_interface::state::{LegacyTokenAccount, TokenAccount};
+
+use crate::fixtures::{Bank, PROGRAM_ID};
+
+/// A mint with `supply` and its authority, a payer, and an owner of a legacy
+/// account holding `legacy`.
+fn setup(legacy: LegacyTokenAccount) -> (Bank, Pubkey, Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mut bank = Bank::new();
+    let authority = bank.add_payer();
+    let mint = bank.add_mint(&authority);
+    let payer = bank.add_payer();
+    let owner = Pubkey::new_unique();
+    let legacy = bank.add_legacy_account(&owner, &legacy);
+    (bank, mint, authority, payer, owner, legacy)
+}
+
+fn legacy(balance: u64, frozen: u64) -> LegacyTokenAccount {
+    LegacyTokenAccount {
+        balance,
+        frozen,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn migrate_creates_the_token_account() {
+    let (mut bank, mint, authority, payer, owner, legacy) = setup(legacy(70, 30));
+    let rent = bank.account(&legacy).lamports;
+    let ix = migrate(PROGRAM_ID, &mint, &authority, &payer, &owner);
+    bank.process(&ix, &[authority, payer]).unwrap();
+    let account = bank.token_account(&owner, &mint);
+    assert_eq!((account.balance, account.frozen), (70, 30));
+    assert_eq!(bank.mint(&mint).supply, 100);
+    assert!(bank.account(&legacy).data.is_empty());
+    assert_eq!(bank.account(&owner).lamports, rent);
+}
+
+#[test]
+fn migrate_adds_to_an_existing_account() {
+    let (mut bank, mint, authority, payer, owner, _) = setup(legacy(70, 0));
+    bank.add_token_account(&owner, &mint, 5);
+    let ix = migrate(PROGRAM_ID, &mint, &authority, &payer, &owner);
+    bank.process(&ix, &[authority, payer]).unwrap();
+    assert_eq!(bank.token_account(&owner, &mint).balance, 75);
+}
+
+#[test]
+fn migrate_refuses_open_credit() {
+    for legacy in [
+        LegacyTokenAccount {
+            borrowed: 1,
+            ..legacy(70, 0)
+        },
+        LegacyTokenAccount {
+            debit: 1,
+            ..legacy(70, 0)
+        },
+    ] {
+        let (mut bank, mint, authority, payer, owner, legacy) = setup(legacy);
+        let ix = migrate(PROGRAM_ID, &mint, &authority, &payer, &owner);
+        assert_eq!(
+            bank.process(&ix, &[authority, payer]),
+            Err(TokenError::OpenCredit.into())
+        );
+        assert_eq!(bank.account(&legacy).data.len(), LegacyTokenAccount::LEN);
+    }
+}
+
+#[test]
+fn migrate_refuses_a_frozen_account() {
+    let (mut bank, mint, authority, payer, owner, _) = setup(legacy(70, 0));
+    let account = bank.add_token_account(&owner, &mint, 5);
+    bank.set_token_account(
+        &account,
+        &TokenAccount {
+            balance: 5,
+            freeze_reason: Some(1),
+            ..TokenAccount::new(owner, mint)
+        },
+    );
+    let ix = migrate(PROGRAM_ID, &mint, &authority, &payer, &owner);
+    assert_eq!(
+        bank.process(&ix, &[authority, payer]),
+        Err(TokenError::AccountFrozen.into())
+    );
+    assert_eq!(bank.token_account(&owner, &mint).balance, 5);
+}
+
+#[test]
+fn migrate_needs_the_mint_authority() {
+    let (mut bank, mint, _, payer, owner, _) = setup(legacy(70, 0));
+    let ix = migrate(PROGRAM_ID, &mint, &payer, &payer, &owner);
+    assert_eq!(
+        bank.process(&ix, &[payer]),
+        Err(TokenError::NotMintAuthority.into())
+    );
+}
@@ -0,0 +1,82 @@
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use tokens_interface::error::TokenError;
+use tokens_interface::instruction::{transfer, with_multisig};
+
+use crate::fixtures::{Bank, PROGRAM_ID};
+
+/// A 2 of 3 multisig holding 100 tokens and a recipient account.
+fn setup() -> (Bank, Pubkey, Pubkey, [Pubkey; 3], Pubkey) {
+    let mut bank = Bank::new();
+    let mint = bank.add_mint(&Pubkey::new_unique());
+    let keys = [bank.add_payer(), bank.add_payer(), bank.add_payer()];
+    let multisig = bank.add_multisig(&mint, 2, &keys);
+    bank.add_token_account(&multisig, &mint, 100);
+    let to = Pubkey::new_unique();
+    bank.add_token_account(&to, &mint, 0);
+    (bank, mint, multisig, keys, to)
+}
+
+#[test]
+fn m_signers_transfer() {
+    let (mut bank, mint, multisig, keys, to) = setup();
+    let ix = with_multisig(
+        transfer(PROGRAM_ID, &mint, &multisig, &to, 40, &mint),
+        &keys[1..],
+    );
+    bank.process(&ix, &keys[1..]).unwrap();
+    assert_eq!(bank.token_account(&multisig, &mint).balance, 60);
+    assert_eq!(bank.token_account(&to, &mint).balance, 40);
+}
+
+#[test]
+fn fewer_than_m_signers_fail() {
+    let (mut bank, mint, multisig, keys, to) = setup();
+    //the second key is listed but does not sign
+    let ix = with_multisig(
+        transfer(PROGRAM_ID, &mint, &multisig, &to, 40, &mint),
+        &keys[..2],
+    );
+    assert_eq!(
+        bank.process(&ix, &keys[..1]),
+        Err(TokenError::NotEnoughSigners.into())
+    );
+    assert_eq!(bank.token_account(&multisig, &mint).balance, 100);
+}
+
+#[test]
+fn multisig_signature_alone_is_rejected() {
+    let (mut bank, mint, multisig, _, to) = setup();
+    let ix = transfer(PROGRAM_ID, &mint, &multisig, &to, 40, &mint);
+    assert_eq!(
+        bank.process(&ix, &[multisig]),
+        Err(TokenError::NotEnoughSigners.into())
+    );
+    assert_eq!(bank.token_account(&to, &mint).balance, 0);
+}
+
+#[test]
+fn multisig_signature_does_not_count_as_a_key() {
+    let (mut bank, mint, multisig, keys, to) = setup();
+    let ix = with_multisig(
+        transfer(PROGRAM_ID, &mint, &multisig, &to, 40, &mint),
+        &[keys[0], multisig],
+    );
+    assert_eq!(
+        bank.process(&ix, &[keys[0], multisig]),
+        Err(TokenError::NotEnoughSigners.into())
+    );
+}
+
+#[test]
+fn single_owner_must_sign() {
+    let (mut bank, mint, _, _, to) = setup();
+    let owner = Pubkey::new_unique();
+    bank.add_token_account(&owner, &mint, 10);
+    let ix = transfer(PROGRAM_ID, &mint, &owner, &to, 5, &mint);
+    assert_eq!(
+        bank.process(&ix, &[]),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+    bank.process(&ix, &[owner]).unwrap();
+    assert_eq!(bank.token_account(&to, &mint).balance, 5);
+}
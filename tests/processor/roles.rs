@@ -0,0 +1,106 @@
+use solana_program::pubkey::Pubkey;
+use tokens_interface::error::TokenError;
+use tokens_interface::instruction::{grant_role, migrate, revoke_role, set_config_roles};
+use tokens_interface::state::{LegacyTokenAccount, Role};
+
+use crate::fixtures::{Bank, PROGRAM_ID};
+
+/// A config of `admin` and a mint using config roles, with a legacy account of
+/// an owner holding 10.
+fn setup() -> (Bank, Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mut bank = Bank::new();
+    let admin = bank.add_payer();
+    bank.add_config(&admin);
+    let authority = bank.add_payer();
+    let mint = bank.add_mint(&authority);
+    bank.process(
+        &set_config_roles(PROGRAM_ID, &mint, &authority, true),
+        &[authority],
+    )
+    .unwrap();
+    let owner = Pubkey::new_unique();
+    bank.add_legacy_account(
+        &owner,
+        &LegacyTokenAccount {
+            balance: 10,
+            ..Default::default()
+        },
+    );
+    (bank, admin, authority, mint, owner)
+}
+
+#[test]
+fn grant_and_revoke_every_role() {
+    let (mut bank, admin, ..) = setup();
+    for role in Role::ALL {
+        let holder = Pubkey::new_unique();
+        bank.process(&grant_role(PROGRAM_ID, &admin, role, &holder), &[admin])
+            .unwrap();
+        assert_eq!(bank.config().role(role), Some(holder));
+        bank.process(&revoke_role(PROGRAM_ID, &admin, role), &[admin])
+            .unwrap();
+        assert_eq!(bank.config().role(role), None);
+    }
+}
+
+#[test]
+fn only_the_admin_grants() {
+    let (mut bank, _, authority, ..) = setup();
+    assert_eq!(
+        bank.process(
+            &grant_role(PROGRAM_ID, &authority, Role::Upgrader, &authority),
+            &[authority]
+        ),
+        Err(TokenError::NotAdmin.into())
+    );
+}
+
+#[test]
+fn upgrader_migrates_instead_of_the_mint_authority() {
+    let (mut bank, admin, authority, mint, owner) = setup();
+    let upgrader = bank.add_payer();
+    bank.process(
+        &grant_role(PROGRAM_ID, &admin, Role::Upgrader, &upgrader),
+        &[admin],
+    )
+    .unwrap();
+    assert_eq!(
+        bank.process(
+            &migrate(PROGRAM_ID, &mint, &authority, &authority, &owner),
+            &[authority]
+        ),
+        Err(TokenError::NotMintAuthority.into())
+    );
+    bank.process(
+        &migrate(PROGRAM_ID, &mint, &upgrader, &upgrader, &owner),
+        &[upgrader],
+    )
+    .unwrap();
+    assert_eq!(bank.token_account(&owner, &mint).balance, 10);
+}
+
+#[test]
+fn revoked_upgrader_hands_migration_back() {
+    let (mut bank, admin, authority, mint, owner) = setup();
+    let upgrader = bank.add_payer();
+    bank.process(
+        &grant_role(PROGRAM_ID, &admin, Role::Upgrader, &upgrader),
+        &[admin],
+    )
+    .unwrap();
+    bank.process(&revoke_role(PROGRAM_ID, &admin, Role::Upgrader), &[admin])
+        .unwrap();
+    assert_eq!(
+        bank.process(
+            &migrate(PROGRAM_ID, &mint, &upgrader, &upgrader, &owner),
+            &[upgrader]
+        ),
+        Err(TokenError::NotMintAuthority.into())
+    );
+    bank.process(
+        &migrate(PROGRAM_ID, &mint, &authority, &authority, &owner),
+        &[authority],
+    )
+    .unwrap();
+    assert_eq!(bank.mint(&mint).supply, 10);
+}
@@ -2,7 +2,43 @@
 pub struct Error(pub &'static str);
 impl std::fmt::Display for Error{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-      f.write_str(&self.0)
+      f.write_str(self.0)
+    }
+}
+impl std::error::Error for Error{}
+/// A transaction the cluster rejected with an error of the tokens or log program.
+#[derive(Debug)]
+pub struct ProgramFailure {
+    /// Index of the failed instruction in the transaction.
+    pub instruction: u8,
+    pub code: u32,
+}
+impl std::fmt::Display for ProgramFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use num_traits::FromPrimitive;
+        use tokens_interface::error::{LogError, TokenError};
+        write!(f, "instruction {} failed: ", self.instruction)?;
+        if let Some(error) = TokenError::from_u32(self.code) {
+            write!(f, "{}", error)
+        } else if let Some(error) = LogError::from_u32(self.code) {
+            write!(f, "{}", error)
+        } else {
+            write!(f, "custom program error {}", self.code)
+        }
+    }
+}
+impl std::error::Error for ProgramFailure {}
+
+/// Turn the error of a failed `send_and_confirm_transaction` into a readable
+/// message when a program returned one of its own errors.
+pub fn map_client_error(
+    error: solana_client::client_error::ClientError,
+) -> Box<dyn std::error::Error> {
+    use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+    match error.get_transaction_error() {
+        Some(TransactionError::InstructionError(instruction, InstructionError::Custom(code))) => {
+            Box::new(ProgramFailure { instruction, code })
+        }
+        _ => Box::new(error),
     }
 }
-impl std::error::Error for Error{}
\ No newline at end of file
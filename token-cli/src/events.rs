@@ -0,0 +1,130 @@
+use std::error::Error;
+
+use base64::Engine;
+use solana_client::rpc_client;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{option_serializer::OptionSerializer, UiTransactionEncoding};
+use tokens_interface::event::Event;
+
+/// Decode the events that `program_ids` logged in `log_messages`. Data logged
+/// by other programs, including the ones they invoke, is skipped.
+pub fn parse_events(program_ids: &[Pubkey], log_messages: &[String]) -> Vec<Event> {
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for line in log_messages {
+        let Some(line) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        if let Some(data) = line.strip_prefix("data: ") {
+            let emitted_by_us = stack
+                .last()
+                .is_some_and(|id| program_ids.iter().any(|pid| pid.to_string() == *id));
+            if !emitted_by_us {
+                continue;
+            }
+            let event = data
+                .split_whitespace()
+                .next()
+                .and_then(|field| base64::engine::general_purpose::STANDARD.decode(field).ok())
+                .and_then(|raw| Event::unpack(&raw).ok());
+            events.extend(event);
+            continue;
+        }
+        let mut words = line.split_whitespace();
+        match (words.next(), words.next()) {
+            (Some(id), Some("invoke")) => stack.push(id),
+            (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+/// Fetch a confirmed transaction and decode the events `program_ids` logged in it.
+pub fn read_events(
+    c: &rpc_client::RpcClient,
+    program_ids: &[Pubkey],
+    signature: &Signature,
+) -> Result<Vec<Event>, Box<dyn Error>> {
+    let tx = c.get_transaction(signature, UiTransactionEncoding::Json)?;
+    let logs = match tx.transaction.meta.map(|meta| meta.log_messages) {
+        Some(OptionSerializer::Some(logs)) => logs,
+        _ => {
+            return Err(Box::new(crate::error::Error(
+                "transaction has no log messages",
+            )))
+        }
+    };
+    Ok(parse_events(program_ids, &logs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(event: &Event) -> String {
+        format!(
+            "Program data: {}",
+            base64::engine::general_purpose::STANDARD.encode(event.pack())
+        )
+    }
+
+    #[test]
+    fn parse_events_of_the_programs_only() {
+        let tokens = Pubkey::new_unique();
+        let log = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let transfer = Event::Transfer {
+            from: Pubkey::new_unique(),
+            to: Pubkey::new_unique(),
+            amount: 10,
+            fee: 1,
+        };
+        let updated = Event::LogUpdated {
+            log: Pubkey::new_unique(),
+            direction: 0,
+            amount: 10,
+            interest: 0,
+        };
+        let ignored = Event::Pause {
+            pauser: Pubkey::new_unique(),
+        };
+        let logs = [
+            format!("Program {} invoke [1]", tokens),
+            "Program log: Instruction: Borrow".to_string(),
+            format!("Program {} invoke [2]", log),
+            data(&updated),
+            format!("Program {} consumed 100 of 200000 compute units", log),
+            format!("Program {} success", log),
+            format!("Program {} invoke [2]", other),
+            data(&ignored),
+            format!("Program {} success", other),
+            data(&transfer),
+            "Program data: not-base64 ZGF0YQ==".to_string(),
+            format!("Program {} success", tokens),
+            data(&ignored),
+        ];
+        assert_eq!(parse_events(&[tokens, log], &logs), vec![updated, transfer]);
+        assert_eq!(parse_events(&[tokens], &logs), vec![transfer]);
+        assert_eq!(parse_events(&[other], &logs), vec![ignored]);
+    }
+
+    #[test]
+    fn parse_events_after_a_failed_invoke() {
+        let tokens = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let thaw = Event::ThawAccount {
+            account: Pubkey::new_unique(),
+        };
+        let logs = [
+            format!("Program {} invoke [1]", tokens),
+            format!("Program {} invoke [2]", other),
+            format!("Program {} failed: custom program error: 0x1", other),
+            data(&thaw),
+        ];
+        assert_eq!(parse_events(&[tokens], &logs), vec![thaw]);
+    }
+}
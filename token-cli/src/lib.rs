@@ -1,80 +1,702 @@
 use std::error::Error;
 
-use solana_sdk::{instruction::AccountMeta, sysvar::SysvarId};
-macro_rules! get_token_account {
-    ($src:ident,$id:ident) => {
-        solana_program::pubkey::Pubkey::find_program_address(&[$src.as_ref()], &$id).0
-    };
-}
+use solana_sdk::{signature::Keypair, signer::Signer};
+use tokens_interface::instruction::{get_config_account, get_metadata_account, get_token_account};
+use tokens_interface::state::{Config, Metadata, Mint, Role, Stream, TokenAccount, Vesting};
 pub mod error;
+pub mod events;
 pub mod logs;
+pub mod migrate;
+pub mod multisig;
 pub fn airdrop(
     program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    authority: &solana_program::pubkey::Pubkey,
     dest: &solana_program::pubkey::Pubkey,
     amount: u64,
 ) -> solana_program::instruction::Instruction {
-    let mut data = Vec::with_capacity(80);
-    data.push(2);
-    data.extend_from_slice(&amount.to_be_bytes());
-    solana_program::instruction::Instruction {
-        program_id: program_id.clone(),
-        accounts: vec![
-            AccountMeta::new(dest.clone(), true),
-            AccountMeta::new(get_token_account!(dest, program_id), false),
-            AccountMeta::new_readonly(program_id, false),
-        ],
-        data: data,
-    }
+    tokens_interface::instruction::mint_to(program_id, mint, authority, dest, amount)
+}
+/// The mint is a new keypair account, returned so it can sign the transaction.
+pub fn init_mint(
+    program_id: solana_program::pubkey::Pubkey,
+    authority: &solana_program::pubkey::Pubkey,
+    supply_cap: Option<u64>,
+) -> (Keypair, solana_program::instruction::Instruction) {
+    let mint = Keypair::new();
+    let ix =
+        tokens_interface::instruction::init_mint(program_id, &mint.pubkey(), authority, supply_cap);
+    (mint, ix)
+}
+pub fn init_mint_with_authority(
+    program_id: solana_program::pubkey::Pubkey,
+    payer: &solana_program::pubkey::Pubkey,
+    authority: &solana_program::pubkey::Pubkey,
+    supply_cap: Option<u64>,
+) -> (Keypair, solana_program::instruction::Instruction) {
+    let mint = Keypair::new();
+    let ix = tokens_interface::instruction::init_mint_with_authority(
+        program_id,
+        &mint.pubkey(),
+        payer,
+        authority,
+        supply_cap,
+    );
+    (mint, ix)
 }
 pub fn init_account(
     program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
     authority: &solana_program::pubkey::Pubkey,
 ) -> solana_program::instruction::Instruction {
-    // let (account, _) =
-    let (account, bump) =
-        solana_program::pubkey::Pubkey::find_program_address(&[authority.as_ref()], &program_id);
-    println!("pda {}", account.to_string());
-    solana_program::instruction::Instruction::new_with_bytes(
-        program_id,
-        &[0, bump],
-        vec![
-            AccountMeta::new(authority.clone(), true),
-            AccountMeta::new(account, false),
-            AccountMeta::new_readonly(solana_sdk::rent::Rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-    )
+    let (account, _) = get_token_account(authority, mint, &program_id);
+    println!("pda {}", account);
+    tokens_interface::instruction::init_account(program_id, mint, authority)
 }
 pub fn get_balance(
     program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
     target: &solana_program::pubkey::Pubkey,
     c: &solana_client::rpc_client::RpcClient,
 ) -> Result<u64, Box<dyn Error>> {
-    let account = get_token_account!(target, program_id);
-    let data = c.get_account_data(&account)?;
-    let dd = data.as_slice();
-    let mut d: [u8; 8] = [0u8; 8];
-    d.copy_from_slice(&dd[0..8]);
-    Ok(u64::from_be_bytes(d))
+    let (account, _) = get_token_account(target, mint, &program_id);
+    let account = c.get_account(&account)?;
+    if account.owner != program_id {
+        return Err(Box::new(crate::error::Error(
+            "account is not token account",
+        )));
+    }
+    let state = TokenAccount::unpack(&account.data)
+        .map_err(|_| crate::error::Error("account is not token account"))?;
+    Ok(state.balance)
+}
+pub fn read_token_account(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    target: &solana_program::pubkey::Pubkey,
+    c: &solana_client::rpc_client::RpcClient,
+) -> Result<TokenAccount, Box<dyn Error>> {
+    let (account, _) = get_token_account(target, mint, &program_id);
+    let account = c.get_account(&account)?;
+    if account.owner != program_id {
+        return Err(Box::new(crate::error::Error(
+            "account is not token account",
+        )));
+    }
+    Ok(TokenAccount::unpack(&account.data)
+        .map_err(|_| crate::error::Error("account is not token account"))?)
 }
 pub fn transfer(
     program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
     from: &solana_program::pubkey::Pubkey,
     to: &solana_program::pubkey::Pubkey,
     amount: u64,
+    treasury: &solana_program::pubkey::Pubkey,
 ) -> solana_program::instruction::Instruction {
-    let mut data = Vec::with_capacity(80);
-    let auth = from.as_ref();
-    // let account =get_token_account!(auth);
-    // solana_program::pubkey::Pubkey::find_program_address(&[auth, b"fadfffdasfas"], &id());
-
-    let accounts = vec![
-        AccountMeta::new(from.clone(), true),
-        AccountMeta::new(get_token_account!(auth, program_id), false),
-        AccountMeta::new_readonly(program_id, false),
-        AccountMeta::new(get_token_account!(to, program_id), false),
+    tokens_interface::instruction::transfer(program_id, mint, from, to, amount, treasury)
+}
+pub fn burn(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    owner: &solana_program::pubkey::Pubkey,
+    amount: u64,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::burn(program_id, mint, owner, amount)
+}
+pub fn freeze(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    owner: &solana_program::pubkey::Pubkey,
+    amount: u64,
+    unlock_at: Option<i64>,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::freeze(program_id, mint, owner, amount, unlock_at)
+}
+pub fn thaw(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    signer: &solana_program::pubkey::Pubkey,
+    owner: &solana_program::pubkey::Pubkey,
+    amount: u64,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::thaw(program_id, mint, signer, owner, amount)
+}
+pub fn freeze_account(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    freezer: &solana_program::pubkey::Pubkey,
+    owner: &solana_program::pubkey::Pubkey,
+    reason: u16,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::freeze_account(program_id, mint, freezer, owner, reason)
+}
+pub fn thaw_account(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    freezer: &solana_program::pubkey::Pubkey,
+    owner: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::thaw_account(program_id, mint, freezer, owner)
+}
+pub fn borrow(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    borrower: &solana_program::pubkey::Pubkey,
+    lender: &solana_program::pubkey::Pubkey,
+    amount: u64,
+    treasury: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::borrow(
+        program_id,
+        tokens_interface::LOG_PROGRAM_ID,
+        mint,
+        borrower,
+        lender,
+        amount,
+        treasury,
+    )
+}
+pub fn repay(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    debtor: &solana_program::pubkey::Pubkey,
+    creditor: &solana_program::pubkey::Pubkey,
+    amount: u64,
+    treasury: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::repay(
+        program_id,
+        tokens_interface::LOG_PROGRAM_ID,
+        mint,
+        debtor,
+        creditor,
+        amount,
+        treasury,
+    )
+}
+pub fn set_rate(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    lender: &solana_program::pubkey::Pubkey,
+    borrower: &solana_program::pubkey::Pubkey,
+    rate_bps: u16,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::set_rate(
+        program_id,
+        tokens_interface::LOG_PROGRAM_ID,
+        mint,
+        lender,
+        borrower,
+        rate_bps,
+    )
+}
+pub fn accrue(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    x: &solana_program::pubkey::Pubkey,
+    y: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::log_instruction::accrue(
+        tokens_interface::LOG_PROGRAM_ID,
+        &get_token_account(x, mint, &program_id).0,
+        &get_token_account(y, mint, &program_id).0,
+    )
+}
+pub fn set_credit_limit(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    lender: &solana_program::pubkey::Pubkey,
+    borrower: &solana_program::pubkey::Pubkey,
+    limit: u64,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::set_credit_limit(
+        program_id,
+        tokens_interface::LOG_PROGRAM_ID,
+        mint,
+        lender,
+        borrower,
+        limit,
+    )
+}
+pub fn set_terms(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    lender: &solana_program::pubkey::Pubkey,
+    borrower: &solana_program::pubkey::Pubkey,
+    ratio_bps: u16,
+    due_at: Option<i64>,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::set_terms(
+        program_id,
+        tokens_interface::LOG_PROGRAM_ID,
+        mint,
+        lender,
+        borrower,
+        ratio_bps,
+        due_at,
+    )
+}
+pub fn lock_collateral(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    borrower: &solana_program::pubkey::Pubkey,
+    lender: &solana_program::pubkey::Pubkey,
+    amount: u64,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::lock_collateral(
+        program_id,
+        tokens_interface::LOG_PROGRAM_ID,
+        mint,
+        borrower,
+        lender,
+        amount,
+    )
+}
+pub fn release_collateral(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    borrower: &solana_program::pubkey::Pubkey,
+    lender: &solana_program::pubkey::Pubkey,
+    amount: u64,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::release_collateral(
+        program_id,
+        tokens_interface::LOG_PROGRAM_ID,
+        mint,
+        borrower,
+        lender,
+        amount,
+    )
+}
+pub fn liquidate(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    caller: &solana_program::pubkey::Pubkey,
+    borrower: &solana_program::pubkey::Pubkey,
+    lender: &solana_program::pubkey::Pubkey,
+    treasury: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::liquidate(
+        program_id,
+        tokens_interface::LOG_PROGRAM_ID,
+        mint,
+        caller,
+        borrower,
+        lender,
+        treasury,
+    )
+}
+pub fn approve(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    owner: &solana_program::pubkey::Pubkey,
+    delegate: &solana_program::pubkey::Pubkey,
+    amount: u64,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::approve(program_id, mint, owner, delegate, amount)
+}
+pub fn revoke(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    owner: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::revoke(program_id, mint, owner)
+}
+pub fn transfer_from(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    delegate: &solana_program::pubkey::Pubkey,
+    owner: &solana_program::pubkey::Pubkey,
+    to: &solana_program::pubkey::Pubkey,
+    amount: u64,
+    treasury: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::transfer_from(
+        program_id, mint, delegate, owner, to, amount, treasury,
+    )
+}
+pub fn init_multisig(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    payer: &solana_program::pubkey::Pubkey,
+    m: u8,
+    signers: &[solana_program::pubkey::Pubkey],
+) -> (
+    solana_program::pubkey::Pubkey,
+    [solana_program::instruction::Instruction; 2],
+) {
+    let (multisig, _) =
+        tokens_interface::instruction::get_multisig_account(m, signers, &program_id);
+    let ixs = [
+        tokens_interface::instruction::init_multisig(program_id, payer, m, signers),
+        tokens_interface::instruction::init_account_for(program_id, mint, payer, &multisig),
     ];
-    data.push(1);
-    data.extend_from_slice(&amount.to_be_bytes());
-    solana_program::instruction::Instruction::new_with_bytes(program_id, &data, accounts)
+    (multisig, ixs)
+}
+/// Sign `ix` built for a multisig account with its co-signers instead.
+pub fn with_multisig(
+    ix: solana_program::instruction::Instruction,
+    signers: &[solana_program::pubkey::Pubkey],
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::with_multisig(ix, signers)
+}
+pub fn close_account(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    owner: &solana_program::pubkey::Pubkey,
+    destination: &solana_program::pubkey::Pubkey,
+    counterparties: &[solana_program::pubkey::Pubkey],
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::close_account(
+        program_id,
+        tokens_interface::LOG_PROGRAM_ID,
+        mint,
+        owner,
+        destination,
+        counterparties,
+    )
+}
+/// Split `payments` into batch transfers that each fit in a transaction paid by `from`.
+pub fn transfer_batch(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    from: &solana_program::pubkey::Pubkey,
+    payments: &[(solana_program::pubkey::Pubkey, u64)],
+    treasury: &solana_program::pubkey::Pubkey,
+) -> Vec<solana_program::instruction::Instruction> {
+    let mut ixs = Vec::new();
+    let mut start = 0;
+    while start < payments.len() {
+        let mut end = start + 1;
+        while end < payments.len()
+            && end - start < u8::MAX as usize
+            && batch_fits(program_id, mint, from, &payments[start..=end], treasury)
+        {
+            end += 1;
+        }
+        ixs.push(tokens_interface::instruction::transfer_batch(
+            program_id,
+            mint,
+            from,
+            &payments[start..end],
+            treasury,
+        ));
+        start = end;
+    }
+    ixs
+}
+fn batch_fits(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    from: &solana_program::pubkey::Pubkey,
+    payments: &[(solana_program::pubkey::Pubkey, u64)],
+    treasury: &solana_program::pubkey::Pubkey,
+) -> bool {
+    let ix =
+        tokens_interface::instruction::transfer_batch(program_id, mint, from, payments, treasury);
+    let tx = solana_sdk::transaction::Transaction::new_with_payer(&[ix], Some(from));
+    bincode::serialized_size(&tx)
+        .is_ok_and(|size| size as usize <= solana_sdk::packet::PACKET_DATA_SIZE)
+}
+pub fn lock_htlc(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    sender: &solana_program::pubkey::Pubkey,
+    recipient: &solana_program::pubkey::Pubkey,
+    amount: u64,
+    hash: [u8; 32],
+    expiry_slot: u64,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::lock_htlc(
+        program_id,
+        mint,
+        sender,
+        recipient,
+        amount,
+        hash,
+        expiry_slot,
+    )
+}
+pub fn claim_htlc(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    caller: &solana_program::pubkey::Pubkey,
+    sender: &solana_program::pubkey::Pubkey,
+    recipient: &solana_program::pubkey::Pubkey,
+    preimage: [u8; 32],
+    treasury: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::claim_htlc(
+        program_id, mint, caller, sender, recipient, preimage, treasury,
+    )
+}
+pub fn reclaim_htlc(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    sender: &solana_program::pubkey::Pubkey,
+    recipient: &solana_program::pubkey::Pubkey,
+    hash: &[u8; 32],
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::reclaim_htlc(program_id, mint, sender, recipient, hash)
+}
+pub fn create_vesting(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    grantor: &solana_program::pubkey::Pubkey,
+    beneficiary: &solana_program::pubkey::Pubkey,
+    total: u64,
+    schedule: (i64, i64, i64),
+    revocable: bool,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::create_vesting(
+        program_id,
+        mint,
+        grantor,
+        beneficiary,
+        total,
+        schedule,
+        revocable,
+    )
+}
+pub fn release(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    caller: &solana_program::pubkey::Pubkey,
+    grantor: &solana_program::pubkey::Pubkey,
+    beneficiary: &solana_program::pubkey::Pubkey,
+    treasury: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::release(program_id, mint, caller, grantor, beneficiary, treasury)
+}
+pub fn revoke_vesting(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    grantor: &solana_program::pubkey::Pubkey,
+    beneficiary: &solana_program::pubkey::Pubkey,
+    treasury: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::revoke_vesting(program_id, mint, grantor, beneficiary, treasury)
+}
+pub fn read_vesting(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    c: &solana_client::rpc_client::RpcClient,
+    grantor: &solana_program::pubkey::Pubkey,
+    beneficiary: &solana_program::pubkey::Pubkey,
+) -> Result<Vesting, Box<dyn Error>> {
+    let (account, _) =
+        tokens_interface::instruction::get_vesting_account(grantor, beneficiary, mint, &program_id);
+    let account = c.get_account(&account)?;
+    if account.owner != program_id {
+        return Err(Box::new(crate::error::Error(
+            "account is not vesting account",
+        )));
+    }
+    Ok(Vesting::unpack(&account.data)
+        .map_err(|_| crate::error::Error("account is not vesting account"))?)
+}
+pub fn create_stream(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    sender: &solana_program::pubkey::Pubkey,
+    recipient: &solana_program::pubkey::Pubkey,
+    deposit: u64,
+    rate: u64,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::create_stream(program_id, mint, sender, recipient, deposit, rate)
+}
+pub fn withdraw(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    recipient: &solana_program::pubkey::Pubkey,
+    sender: &solana_program::pubkey::Pubkey,
+    treasury: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::withdraw(program_id, mint, recipient, sender, treasury)
+}
+pub fn cancel_stream(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    signer: &solana_program::pubkey::Pubkey,
+    sender: &solana_program::pubkey::Pubkey,
+    recipient: &solana_program::pubkey::Pubkey,
+    treasury: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::cancel_stream(
+        program_id, mint, signer, sender, recipient, treasury,
+    )
+}
+pub fn read_stream(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    c: &solana_client::rpc_client::RpcClient,
+    sender: &solana_program::pubkey::Pubkey,
+    recipient: &solana_program::pubkey::Pubkey,
+) -> Result<Stream, Box<dyn Error>> {
+    let (account, _) =
+        tokens_interface::instruction::get_stream_account(sender, recipient, mint, &program_id);
+    let account = c.get_account(&account)?;
+    if account.owner != program_id {
+        return Err(Box::new(crate::error::Error(
+            "account is not stream account",
+        )));
+    }
+    Ok(Stream::unpack(&account.data)
+        .map_err(|_| crate::error::Error("account is not stream account"))?)
+}
+pub fn read_mint(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    c: &solana_client::rpc_client::RpcClient,
+) -> Result<Mint, Box<dyn Error>> {
+    let account = c.get_account(mint)?;
+    if account.owner != program_id {
+        return Err(Box::new(crate::error::Error("account is not mint account")));
+    }
+    Ok(Mint::unpack(&account.data)
+        .map_err(|_| crate::error::Error("account is not mint account"))?)
+}
+/// Withhold `fee_bps` of every transfer, at most `max_fee`, into the token account of `treasury`.
+pub fn set_transfer_fee(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    authority: &solana_program::pubkey::Pubkey,
+    fee_bps: u16,
+    max_fee: u64,
+    treasury: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    let (treasury, _) = get_token_account(treasury, mint, &program_id);
+    tokens_interface::instruction::set_transfer_fee(
+        program_id, mint, authority, fee_bps, max_fee, &treasury,
+    )
+}
+pub fn set_config_roles(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    authority: &solana_program::pubkey::Pubkey,
+    enabled: bool,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::set_config_roles(program_id, mint, authority, enabled)
+}
+pub fn init_config(
+    program_id: solana_program::pubkey::Pubkey,
+    payer: &solana_program::pubkey::Pubkey,
+    admin: Option<&solana_program::pubkey::Pubkey>,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::init_config(program_id, payer, admin)
+}
+pub fn pause(
+    program_id: solana_program::pubkey::Pubkey,
+    admin: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::pause(program_id, admin)
+}
+pub fn unpause(
+    program_id: solana_program::pubkey::Pubkey,
+    admin: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::unpause(program_id, admin)
+}
+pub fn grant_role(
+    program_id: solana_program::pubkey::Pubkey,
+    admin: &solana_program::pubkey::Pubkey,
+    role: Role,
+    holder: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::grant_role(program_id, admin, role, holder)
+}
+pub fn revoke_role(
+    program_id: solana_program::pubkey::Pubkey,
+    admin: &solana_program::pubkey::Pubkey,
+    role: Role,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::revoke_role(program_id, admin, role)
+}
+pub fn read_config(
+    program_id: solana_program::pubkey::Pubkey,
+    c: &solana_client::rpc_client::RpcClient,
+) -> Result<Config, Box<dyn Error>> {
+    let (account, _) = get_config_account(&program_id);
+    let account = c.get_account(&account)?;
+    if account.owner != program_id {
+        return Err(Box::new(crate::error::Error(
+            "account is not config account",
+        )));
+    }
+    Ok(Config::unpack(&account.data)
+        .map_err(|_| crate::error::Error("account is not config account"))?)
+}
+pub fn init_metadata(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    authority: &solana_program::pubkey::Pubkey,
+    decimals: u8,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::init_metadata(
+        program_id, mint, authority, decimals, name, symbol, uri,
+    )
+}
+pub fn update_metadata(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    authority: &solana_program::pubkey::Pubkey,
+    new_authority: &solana_program::pubkey::Pubkey,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::update_metadata(
+        program_id,
+        mint,
+        authority,
+        new_authority,
+        name,
+        symbol,
+        uri,
+    )
+}
+pub fn read_metadata(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    c: &solana_client::rpc_client::RpcClient,
+) -> Result<Metadata, Box<dyn Error>> {
+    let (account, _) = get_metadata_account(mint, &program_id);
+    let account = c.get_account(&account)?;
+    if account.owner != program_id {
+        return Err(Box::new(crate::error::Error(
+            "account is not metadata account",
+        )));
+    }
+    Ok(Metadata::unpack(&account.data)
+        .map_err(|_| crate::error::Error("account is not metadata account"))?)
+}
+/// Metadata to read and show amounts of the mint with, raw integers without
+/// a symbol when the mint has no metadata account.
+pub fn read_units(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    c: &solana_client::rpc_client::RpcClient,
+) -> Result<Metadata, Box<dyn Error>> {
+    let (account, _) = get_metadata_account(mint, &program_id);
+    if c.get_account_with_commitment(&account, c.commitment())?
+        .value
+        .is_none()
+    {
+        return Ok(Metadata::default());
+    }
+    read_metadata(program_id, mint, c)
+}
+/// Rewrite the legacy token account of `owner` as an account of `mint`.
+pub fn migrate(
+    program_id: solana_program::pubkey::Pubkey,
+    mint: &solana_program::pubkey::Pubkey,
+    authority: &solana_program::pubkey::Pubkey,
+    payer: &solana_program::pubkey::Pubkey,
+    owner: &solana_program::pubkey::Pubkey,
+) -> solana_program::instruction::Instruction {
+    tokens_interface::instruction::migrate(program_id, mint, authority, payer, owner)
 }
@@ -2,30 +2,25 @@ use std::error::Error;
 
 use solana_client::rpc_client;
 use solana_program::pubkey::Pubkey;
-use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
-    signature::Keypair,
-    signer::Signer,
-    sysvar::SysvarId,
-};
-static BOOK_SIZE: usize = 32 * 40;
-static LOG_DATA_SIZE: usize = 9 + 64;
-// pub fn init_log_book(log_program_id: &Pubkey, owner: &Pubkey) -> Instruction {
-//     let book_account = Keypair::new();
-//     solana_sdk::instruction::Instruction::new_with_bytes(
-//         log_program_id.clone(),
-//         &[1],
-//         vec![
-//             AccountMeta::new(*owner, true),
-//             AccountMeta::new(book_account.pubkey(), false),
-//             AccountMeta::new_readonly(solana_program::rent::Rent::id(), false),
-//         ],
-//     )
-// }
+use solana_sdk::{clock::Clock, signature::Keypair, signer::Signer, sysvar::SysvarId};
+use tokens_interface::instruction::get_token_account;
+use tokens_interface::log_instruction::{self, BOOK_SIZE, LEGACY_LOG_DATA_SIZE, LOG_DATA_SIZE};
+use tokens_interface::log_state::CreditLog;
 pub struct LogData {
-    creditor: Pubkey,
-    debtor: Pubkey,
-    amount: u64,
+    pub creditor: Pubkey,
+    pub debtor: Pubkey,
+    /// Principal still owed.
+    pub amount: u64,
+    /// Interest accrued up to the current cluster time and not paid yet.
+    pub interest: u64,
+    /// Most the creditor lends the debtor.
+    pub limit: u64,
+    /// Collateral the creditor asks for in basis points of the debt.
+    pub ratio_bps: u16,
+    /// Unix timestamp the debt is due, 0 when it has no due date.
+    pub due_at: i64,
+    /// Frozen balance the debtor locked for the creditor.
+    pub collateral: u64,
 }
 pub fn read_log_book(
     log_program_id: Pubkey,
@@ -44,12 +39,7 @@ pub fn read_log_book(
     let mut result = Vec::with_capacity(32);
     for i in 1..=40 {
         let data = &book_account.data[(i - 1) * 32..i * 32];
-        if data
-            == &[
-                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-                0, 0, 0, 0,
-            ]
-        {
+        if data == [0u8; 32] {
             break;
         }
         dd.copy_from_slice(data);
@@ -67,81 +57,48 @@ pub fn read_log(
     let account = c.get_account(log_key)?;
     if account.owner != log_program_id {
         return Err(Box::new(crate::error::Error("account is not book account")));
-    } else if account.data.len() != LOG_DATA_SIZE {
+    } else if account.data.len() != LOG_DATA_SIZE && account.data.len() != LEGACY_LOG_DATA_SIZE {
         return Err(Box::new(crate::error::Error(
             "log account version not match",
         )));
     }
-    let mut buff = [0u8; 32];
-    buff.copy_from_slice(&account.data[9..41]);
-    let a1 = Pubkey::new_from_array(buff);
-    buff.copy_from_slice(&account.data[41..73]);
-    let a2 = Pubkey::new_from_array(buff);
-    let mut buff = [0u8; 8];
-    buff.copy_from_slice(&account.data[1..9]);
-    let amount = u64::from_be_bytes(buff);
-    match account.data[0] {
-        0 => {
-            //a1=>a2
-            return Ok(LogData {
-                creditor: a1,
-                debtor: a2,
-                amount: amount,
-            });
-        }
-        1 => {
-            //a2=>a1
-            return Ok(LogData {
-                creditor: a2,
-                debtor: a1,
-                amount: amount,
-            });
-        }
-        _ => {
-            return Err(Box::new(crate::error::Error(
-                "account data is invalid error",
-            )));
-        }
-    }
+    let mut log = CreditLog::unpack(&account.data)
+        .map_err(|_| crate::error::Error("account data is invalid error"))?;
+    let clock: Clock = bincode::deserialize(&c.get_account_data(&Clock::id())?)?;
+    log.accrue(clock.unix_timestamp);
+    Ok(LogData {
+        creditor: log.creditor(),
+        debtor: log.debtor(),
+        amount: log.amount,
+        interest: log.interest,
+        limit: log.limits[log.direction as usize],
+        ratio_bps: log.ratios[log.direction as usize],
+        due_at: log.due[log.direction as usize],
+        collateral: log.collaterals[log.direction as usize],
+    })
 }
 
-fn init_log_data_account(
-    program_id: &Pubkey,
+/// Read the credit between the token accounts of two owners.
+pub fn read_credit(
+    program_id: Pubkey,
+    log_program_id: Pubkey,
     c: &rpc_client::RpcClient,
-    payer: &Pubkey,
-    a1: &Pubkey,
-    a2: &Pubkey,
-) {
-    let (pda, bump) = Pubkey::find_program_address(&[a1.as_ref(), a2.as_ref()], program_id);
-    solana_program::instruction::Instruction::new_with_bytes(
-        program_id.clone(),
-        &[],
-        vec![
-            AccountMeta::new(*payer, true),
-            AccountMeta::new(pda, false),
-            AccountMeta::new_readonly(*a1, false),
-            AccountMeta::new_readonly(*a2, false),
-            AccountMeta::new_readonly(solana_program::rent::Rent::id(), false),
-        ],
-    );
-    // solana_program::system_instruction::create_account();
+    mint: &Pubkey,
+    x: &Pubkey,
+    y: &Pubkey,
+) -> Result<LogData, Box<dyn Error>> {
+    let x = get_token_account(x, mint, &program_id).0;
+    let y = get_token_account(y, mint, &program_id).0;
+    let (a1, a2, _) = log_instruction::sort_pair(&x, &y);
+    let (log_key, _) = log_instruction::get_log_account(&a1, &a2, &log_program_id);
+    read_log(log_program_id, c, &log_key)
 }
+
 pub fn init_log_book_account(
     program_id: &Pubkey,
     owner: &Pubkey,
 ) -> (Keypair, solana_program::instruction::Instruction) {
     let kp = Keypair::new();
-    let pubkey = kp.pubkey();
-    (
-        kp,
-        solana_program::instruction::Instruction::new_with_bytes(
-            *program_id,
-            &[],
-            vec![
-                AccountMeta::new(*owner, true),
-                AccountMeta::new(pubkey, false),
-                AccountMeta::new_readonly(solana_program::rent::Rent::id(), false),
-            ],
-        ),
-    )
+    let ix = log_instruction::init_book(*program_id, owner, &kp.pubkey());
+    (kp, ix)
 }
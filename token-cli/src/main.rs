@@ -1,6 +1,7 @@
 use std::error::Error;
 
 use clap::{value_parser, Arg, Command};
+use tokens_interface::state::Role;
 macro_rules! get_program_id {
     ($netype:ident) => {
         match $netype {
@@ -19,11 +20,57 @@ macro_rules! args_init {
 }
 fn main() -> Result<(), Box<dyn Error>> {
     let cmd = Command::new("token-cli").subcommands([
-        airdrop().args(args_init!()),
-        balance().args(args_init!()),
-        create_account().args(args_init!()),
-        transfer().args(args_init!()),
+        airdrop().arg(mint_arg()).args(args_init!()),
+        balance().arg(mint_arg()).args(args_init!()),
+        create_account().arg(mint_arg()).args(args_init!()),
+        transfer().arg(mint_arg()).args(args_init!()),
         create_log_book_account().args(args_init!()),
+        create_mint().args(args_init!()),
+        burn().arg(mint_arg()).args(args_init!()),
+        freeze().arg(mint_arg()).args(args_init!()),
+        thaw().arg(mint_arg()).args(args_init!()),
+        freeze_account().arg(mint_arg()).args(args_init!()),
+        thaw_account().arg(mint_arg()).args(args_init!()),
+        borrow().arg(mint_arg()).args(args_init!()),
+        repay().arg(mint_arg()).args(args_init!()),
+        set_rate().arg(mint_arg()).args(args_init!()),
+        credit().arg(mint_arg()).args(args_init!()),
+        accrue().arg(mint_arg()).args(args_init!()),
+        set_credit_limit().arg(mint_arg()).args(args_init!()),
+        set_terms().arg(mint_arg()).args(args_init!()),
+        lock_collateral().arg(mint_arg()).args(args_init!()),
+        release_collateral().arg(mint_arg()).args(args_init!()),
+        liquidate().arg(mint_arg()).args(args_init!()),
+        approve().arg(mint_arg()).args(args_init!()),
+        revoke().arg(mint_arg()).args(args_init!()),
+        transfer_from().arg(mint_arg()).args(args_init!()),
+        create_multisig().arg(mint_arg()).args(args_init!()),
+        multisig_transfer().arg(mint_arg()).args(args_init!()),
+        multisig_airdrop().arg(mint_arg()).args(args_init!()),
+        sign_transaction(),
+        send_transaction().args(args_init!()),
+        close_account().arg(mint_arg()).args(args_init!()),
+        transfer_batch().arg(mint_arg()).args(args_init!()),
+        htlc_lock().arg(mint_arg()).args(args_init!()),
+        htlc_claim().arg(mint_arg()).args(args_init!()),
+        htlc_reclaim().arg(mint_arg()).args(args_init!()),
+        vesting_create().arg(mint_arg()).args(args_init!()),
+        vesting_release().arg(mint_arg()).args(args_init!()),
+        vesting_revoke().arg(mint_arg()).args(args_init!()),
+        vesting().arg(mint_arg()).args(args_init!()),
+        stream_create().arg(mint_arg()).args(args_init!()),
+        stream_withdraw().arg(mint_arg()).args(args_init!()),
+        stream_cancel().arg(mint_arg()).args(args_init!()),
+        stream().arg(mint_arg()).args(args_init!()),
+        set_transfer_fee().arg(mint_arg()).args(args_init!()),
+        set_config_roles().arg(mint_arg()).args(args_init!()),
+        create_metadata().arg(mint_arg()).args(args_init!()),
+        update_metadata().arg(mint_arg()).args(args_init!()),
+        metadata().arg(mint_arg()).args(args_init!()),
+        migrate().arg(mint_arg()).args(args_init!()),
+        events().args(args_init!()),
+        admin(),
+        roles(),
     ]);
     let matches = cmd.get_matches();
 
@@ -33,11 +80,79 @@ fn main() -> Result<(), Box<dyn Error>> {
         Some(("create-account", am)) => cmd::create_account(am),
         Some(("transfer", am)) => cmd::transfer(am),
         Some(("create-log-book-account", am)) => cmd::create_log_book_account(am),
+        Some(("create-mint", am)) => cmd::create_mint(am),
+        Some(("burn", am)) => cmd::burn(am),
+        Some(("freeze", am)) => cmd::freeze(am),
+        Some(("thaw", am)) => cmd::thaw(am),
+        Some(("freeze-account", am)) => cmd::freeze_account(am),
+        Some(("thaw-account", am)) => cmd::thaw_account(am),
+        Some(("borrow", am)) => cmd::borrow(am),
+        Some(("repay", am)) => cmd::repay(am),
+        Some(("set-rate", am)) => cmd::set_rate(am),
+        Some(("credit", am)) => cmd::credit(am),
+        Some(("accrue", am)) => cmd::accrue(am),
+        Some(("set-credit-limit", am)) => cmd::set_credit_limit(am),
+        Some(("set-terms", am)) => cmd::set_terms(am),
+        Some(("lock-collateral", am)) => cmd::lock_collateral(am),
+        Some(("release-collateral", am)) => cmd::release_collateral(am),
+        Some(("liquidate", am)) => cmd::liquidate(am),
+        Some(("approve", am)) => cmd::approve(am),
+        Some(("revoke", am)) => cmd::revoke(am),
+        Some(("transfer-from", am)) => cmd::transfer_from(am),
+        Some(("create-multisig", am)) => cmd::create_multisig(am),
+        Some(("multisig-transfer", am)) => cmd::multisig_transfer(am),
+        Some(("multisig-airdrop", am)) => cmd::multisig_airdrop(am),
+        Some(("sign-transaction", am)) => cmd::sign_transaction(am),
+        Some(("send-transaction", am)) => cmd::send_transaction(am),
+        Some(("close-account", am)) => cmd::close_account(am),
+        Some(("transfer-batch", am)) => cmd::transfer_batch(am),
+        Some(("htlc-lock", am)) => cmd::htlc_lock(am),
+        Some(("htlc-claim", am)) => cmd::htlc_claim(am),
+        Some(("htlc-reclaim", am)) => cmd::htlc_reclaim(am),
+        Some(("vesting-create", am)) => cmd::vesting_create(am),
+        Some(("vesting-release", am)) => cmd::vesting_release(am),
+        Some(("vesting-revoke", am)) => cmd::vesting_revoke(am),
+        Some(("vesting", am)) => cmd::vesting(am),
+        Some(("stream-create", am)) => cmd::stream_create(am),
+        Some(("stream-withdraw", am)) => cmd::stream_withdraw(am),
+        Some(("stream-cancel", am)) => cmd::stream_cancel(am),
+        Some(("stream", am)) => cmd::stream(am),
+        Some(("set-transfer-fee", am)) => cmd::set_transfer_fee(am),
+        Some(("set-config-roles", am)) => cmd::set_config_roles(am),
+        Some(("create-metadata", am)) => cmd::create_metadata(am),
+        Some(("update-metadata", am)) => cmd::update_metadata(am),
+        Some(("metadata", am)) => cmd::metadata(am),
+        Some(("migrate", am)) => cmd::migrate(am),
+        Some(("events", am)) => cmd::events(am),
+        Some(("admin", am)) => match am.subcommand() {
+            Some(("init", am)) => cmd::admin_init(am),
+            Some(("pause", am)) => cmd::admin_pause(am, true),
+            Some(("unpause", am)) => cmd::admin_pause(am, false),
+            Some(("status", am)) => cmd::admin_status(am),
+            _ => unreachable!(),
+        },
+        Some(("roles", am)) => match am.subcommand() {
+            Some(("list", am)) => cmd::roles_list(am),
+            Some(("grant", am)) => cmd::roles_grant(am),
+            Some(("revoke", am)) => cmd::roles_revoke(am),
+            _ => unreachable!(),
+        },
         _ => Err(Box::new(token_cli::error::Error("not exist command"))),
     }
 }
+fn mint_arg() -> Arg {
+    Arg::new("mint")
+        .long("mint")
+        .value_parser(value_parser!(String))
+        .required(true)
+}
 fn airdrop() -> Command {
     Command::new("airdrop")
+        .arg(
+            Arg::new("Authority")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
         .arg(
             Arg::new("Address")
                 .value_parser(value_parser!(String))
@@ -45,7 +160,7 @@ fn airdrop() -> Command {
         )
         .arg(
             Arg::new("Amount")
-                .value_parser(value_parser!(u64))
+                .value_parser(value_parser!(String))
                 .required(true),
         )
 }
@@ -72,7 +187,7 @@ fn transfer() -> Command {
         )
         .arg(
             Arg::new("Amount")
-                .value_parser(value_parser!(u64))
+                .value_parser(value_parser!(String))
                 .required(true),
         )
         .arg(
@@ -88,107 +203,2103 @@ fn create_log_book_account() -> Command {
             .required(true),
     )
 }
-mod cmd {
-    use std::{error::Error, str::FromStr};
-
-    use clap::ArgMatches;
-    use solana_sdk::{signature::Keypair, signer::Signer};
-    use token_cli::logs;
-
-    pub fn airdrop(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
-        let address = arg_matches.get_one::<String>("Address").unwrap();
-        let address = Keypair::from_base58_string(&address);
-        let amount = arg_matches.get_one::<u64>("Amount").unwrap();
-        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
-        let netype = arg_matches.get_one::<String>("network").unwrap();
-        let nt = netype.as_str();
-        let program_id = get_program_id!(nt);
-        let pubkey = address.pubkey();
-        let ix = token_cli::airdrop(program_id, &pubkey, *amount);
-        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
-        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&pubkey),
-            &[&address],
-            rpc_client.get_latest_blockhash()?,
-        );
-        let sig = rpc_client.send_and_confirm_transaction(&tx)?;
-        println!("{}", sig.to_string());
-        Ok(())
-    }
-    pub fn balance(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
-        let address = arg_matches.get_one::<String>("Address").unwrap();
-        let pubkey = solana_program::pubkey::Pubkey::from_str(&address)?;
-        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
-        let netype = arg_matches.get_one::<String>("network").unwrap();
-        let nt = netype.as_str();
-        let program_id = get_program_id!(nt);
-        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
-        let balance = token_cli::get_balance(program_id, &pubkey, &rpc_client)?;
-        println!("{balance}");
-        Ok(())
-    }
-    pub fn create_account(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
-        let address = arg_matches.get_one::<String>("Address").unwrap();
-        let address = Keypair::from_base58_string(&address);
-        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
-        let netype = arg_matches.get_one::<String>("network").unwrap();
-        let nt = netype.as_str();
-        let program_id = get_program_id!(nt);
-        let pubkey = address.pubkey();
-        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
-        let ix = token_cli::init_account(program_id, &pubkey);
-        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&pubkey),
-            &[&address],
-            rpc_client.get_latest_blockhash()?,
-        );
-        let sig = rpc_client.send_and_confirm_transaction(&tx)?;
-        println!("{}", sig.to_string());
-        Ok(())
-    }
-    pub fn transfer(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
-        let address = arg_matches.get_one::<String>("Address").unwrap();
-        let address = Keypair::from_base58_string(&address);
-        let amount = arg_matches.get_one::<u64>("Amount").unwrap();
-        let dest_address = arg_matches.get_one::<String>("Dest_Address").unwrap();
-        let dest = solana_program::pubkey::Pubkey::from_str(&dest_address)?;
-        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
-        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
-        let netype = arg_matches.get_one::<String>("network").unwrap();
-        let nt = netype.as_str();
-        let program_id = get_program_id!(nt);
-        let pubkey = address.pubkey();
-        let ix = token_cli::transfer(program_id, &pubkey, &dest, *amount);
-        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&pubkey),
-            &[&address],
-            rpc_client.get_latest_blockhash()?,
-        );
-        let sig = rpc_client.send_and_confirm_transaction(&tx)?;
-        println!("{}", sig.to_string());
-        Ok(())
-    }
-    pub fn create_log_book_account(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
-        let address = arg_matches.get_one::<String>("Address").unwrap();
-        let address = Keypair::from_base58_string(&address);
-        let netype = arg_matches.get_one::<String>("network").unwrap();
-        let nt = netype.as_str();
-        let program_id = get_program_id!(nt);
-        let payer = address.pubkey();
-        let (book, ix) = logs::init_log_book_account(&program_id, &payer);
-        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
-        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
-        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&payer),
-            &[address, book],
-            rpc_client.get_latest_blockhash()?,
-        );
-        let sig = rpc_client.send_transaction(&tx)?;
-        println!("{}", sig.to_string());
+fn create_mint() -> Command {
+    Command::new("create-mint")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("supply-cap")
+                .long("supply-cap")
+                .value_parser(value_parser!(u64)),
+        )
+        .arg(
+            Arg::new("authority")
+                .long("authority")
+                .value_parser(value_parser!(String)),
+        )
+}
+fn burn() -> Command {
+    Command::new("burn")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn freeze() -> Command {
+    Command::new("freeze")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("unlock-at")
+                .long("unlock-at")
+                .value_parser(value_parser!(i64)),
+        )
+}
+fn thaw() -> Command {
+    Command::new("thaw")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("owner")
+                .long("owner")
+                .value_parser(value_parser!(String)),
+        )
+}
+fn freeze_account() -> Command {
+    Command::new("freeze-account")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Owner")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("reason")
+                .long("reason")
+                .value_parser(value_parser!(u16))
+                .default_value("0"),
+        )
+}
+fn thaw_account() -> Command {
+    Command::new("thaw-account")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Owner")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn borrow() -> Command {
+    Command::new("borrow")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Lender_Address")
+                .help("keypair of the lender, who signs for the lent amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn repay() -> Command {
+    Command::new("repay")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Lender_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn set_rate() -> Command {
+    Command::new("set-rate")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Borrower_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Rate_Bps")
+                .value_parser(value_parser!(u16))
+                .required(true),
+        )
+}
+fn credit() -> Command {
+    Command::new("credit")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Counterparty_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn accrue() -> Command {
+    Command::new("accrue")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Counterparty_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn set_credit_limit() -> Command {
+    Command::new("set-credit-limit")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Borrower_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Limit")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn set_terms() -> Command {
+    Command::new("set-terms")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Borrower_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Ratio_Bps")
+                .value_parser(value_parser!(u16))
+                .required(true),
+        )
+        .arg(
+            Arg::new("due-at")
+                .long("due-at")
+                .value_parser(value_parser!(i64)),
+        )
+}
+fn lock_collateral() -> Command {
+    Command::new("lock-collateral")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Lender_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn release_collateral() -> Command {
+    Command::new("release-collateral")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Lender_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn liquidate() -> Command {
+    Command::new("liquidate")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Borrower_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Lender_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn approve() -> Command {
+    Command::new("approve")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Delegate_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn revoke() -> Command {
+    Command::new("revoke").arg(
+        Arg::new("Address")
+            .value_parser(value_parser!(String))
+            .required(true),
+    )
+}
+fn transfer_from() -> Command {
+    Command::new("transfer-from")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Owner_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Dest_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+/// Co-signer and fee payer options of the commands building a multisig transaction.
+fn multisig_args() -> [Arg; 2] {
+    [
+        Arg::new("signer")
+            .long("signer")
+            .value_parser(value_parser!(String))
+            .action(clap::ArgAction::Append)
+            .required(true),
+        Arg::new("fee-payer")
+            .long("fee-payer")
+            .value_parser(value_parser!(String)),
+    ]
+}
+fn create_multisig() -> Command {
+    Command::new("create-multisig")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(Arg::new("M").value_parser(value_parser!(u8)).required(true))
+        .arg(
+            Arg::new("Signers")
+                .value_parser(value_parser!(String))
+                .num_args(1..)
+                .required(true),
+        )
+}
+fn multisig_transfer() -> Command {
+    Command::new("multisig-transfer")
+        .arg(
+            Arg::new("Multisig_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Dest_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .args(multisig_args())
+}
+fn multisig_airdrop() -> Command {
+    Command::new("multisig-airdrop")
+        .arg(
+            Arg::new("Multisig_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .args(multisig_args())
+}
+fn sign_transaction() -> Command {
+    Command::new("sign-transaction")
+        .arg(
+            Arg::new("Transaction")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn send_transaction() -> Command {
+    Command::new("send-transaction").arg(
+        Arg::new("Transaction")
+            .value_parser(value_parser!(String))
+            .required(true),
+    )
+}
+fn close_account() -> Command {
+    Command::new("close-account")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("destination")
+                .long("destination")
+                .value_parser(value_parser!(String)),
+        )
+        .arg(
+            Arg::new("counterparty")
+                .long("counterparty")
+                .help("owner the account has borrowed from or lent to")
+                .value_parser(value_parser!(String))
+                .action(clap::ArgAction::Append),
+        )
+}
+fn transfer_batch() -> Command {
+    Command::new("transfer-batch")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Payments")
+                .help("Dest_Address:Amount pairs")
+                .value_parser(value_parser!(String))
+                .num_args(1..)
+                .required(true),
+        )
+}
+fn htlc_lock() -> Command {
+    Command::new("htlc-lock")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Recipient_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Amount")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Hash")
+                .help("base58 sha-256 hash of the preimage")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Expiry_Slot")
+                .value_parser(value_parser!(u64))
+                .required(true),
+        )
+}
+fn htlc_claim() -> Command {
+    Command::new("htlc-claim")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Sender_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Recipient_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Preimage")
+                .help("base58 32 byte preimage")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn htlc_reclaim() -> Command {
+    Command::new("htlc-reclaim")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Recipient_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Hash")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn vesting_create() -> Command {
+    Command::new("vesting-create")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Beneficiary_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Total")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Start")
+                .value_parser(value_parser!(i64))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Cliff")
+                .value_parser(value_parser!(i64))
+                .required(true),
+        )
+        .arg(
+            Arg::new("End")
+                .value_parser(value_parser!(i64))
+                .required(true),
+        )
+        .arg(
+            Arg::new("revocable")
+                .long("revocable")
+                .action(clap::ArgAction::SetTrue),
+        )
+}
+fn vesting_release() -> Command {
+    Command::new("vesting-release")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Grantor_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Beneficiary_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn vesting_revoke() -> Command {
+    Command::new("vesting-revoke")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Beneficiary_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn vesting() -> Command {
+    Command::new("vesting")
+        .arg(
+            Arg::new("Grantor_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Beneficiary_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn stream_create() -> Command {
+    Command::new("stream-create")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Recipient_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Deposit")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Rate")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn stream_withdraw() -> Command {
+    Command::new("stream-withdraw")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Sender_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn stream_cancel() -> Command {
+    Command::new("stream-cancel")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Sender_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Recipient_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn stream() -> Command {
+    Command::new("stream")
+        .arg(
+            Arg::new("Sender_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Recipient_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn set_transfer_fee() -> Command {
+    Command::new("set-transfer-fee")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Fee_Bps")
+                .value_parser(value_parser!(u16))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Max_Fee")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Treasury_Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+}
+fn set_config_roles() -> Command {
+    Command::new("set-config-roles")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Enabled")
+                .help(
+                    "whether the config minter, freezer, fee manager and upgrader act for the mint",
+                )
+                .value_parser(value_parser!(bool))
+                .required(true),
+        )
+}
+fn create_metadata() -> Command {
+    Command::new("create-metadata")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Decimals")
+                .value_parser(value_parser!(u8))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Name")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("Symbol")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("uri")
+                .long("uri")
+                .value_parser(value_parser!(String))
+                .default_value(""),
+        )
+}
+fn update_metadata() -> Command {
+    Command::new("update-metadata")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .value_parser(value_parser!(String)),
+        )
+        .arg(
+            Arg::new("symbol")
+                .long("symbol")
+                .value_parser(value_parser!(String)),
+        )
+        .arg(
+            Arg::new("uri")
+                .long("uri")
+                .value_parser(value_parser!(String)),
+        )
+        .arg(
+            Arg::new("authority")
+                .long("authority")
+                .value_parser(value_parser!(String)),
+        )
+}
+fn metadata() -> Command {
+    Command::new("metadata")
+}
+fn migrate() -> Command {
+    Command::new("migrate")
+        .arg(
+            Arg::new("Address")
+                .value_parser(value_parser!(String))
+                .required(true),
+        )
+        .arg(
+            Arg::new("batch-size")
+                .long("batch-size")
+                .value_parser(value_parser!(usize))
+                .default_value("6"),
+        )
+}
+fn events() -> Command {
+    Command::new("events").arg(
+        Arg::new("Signature")
+            .value_parser(value_parser!(String))
+            .required(true),
+    )
+}
+fn admin() -> Command {
+    Command::new("admin")
+        .subcommand_required(true)
+        .subcommands([
+            Command::new("init")
+                .arg(
+                    Arg::new("Address")
+                        .help("keypair of the program upgrade authority")
+                        .value_parser(value_parser!(String))
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("admin")
+                        .long("admin")
+                        .value_parser(value_parser!(String)),
+                )
+                .args(args_init!()),
+            Command::new("pause")
+                .arg(
+                    Arg::new("Address")
+                        .value_parser(value_parser!(String))
+                        .required(true),
+                )
+                .args(args_init!()),
+            Command::new("unpause")
+                .arg(
+                    Arg::new("Address")
+                        .value_parser(value_parser!(String))
+                        .required(true),
+                )
+                .args(args_init!()),
+            Command::new("status").args(args_init!()),
+        ])
+}
+fn role_arg() -> Arg {
+    Arg::new("Role")
+        .value_parser(Role::ALL.map(|role| role.name()))
+        .required(true)
+}
+fn roles() -> Command {
+    Command::new("roles")
+        .subcommand_required(true)
+        .subcommands([
+            Command::new("list").args(args_init!()),
+            Command::new("grant")
+                .arg(
+                    Arg::new("Address")
+                        .value_parser(value_parser!(String))
+                        .required(true),
+                )
+                .arg(role_arg())
+                .arg(
+                    Arg::new("Holder")
+                        .value_parser(value_parser!(String))
+                        .required(true),
+                )
+                .args(args_init!()),
+            Command::new("revoke")
+                .arg(
+                    Arg::new("Address")
+                        .value_parser(value_parser!(String))
+                        .required(true),
+                )
+                .arg(role_arg())
+                .args(args_init!()),
+        ])
+}
+mod cmd {
+    use std::{error::Error, str::FromStr};
+
+    use clap::ArgMatches;
+    use solana_sdk::{
+        instruction::Instruction,
+        signature::{Keypair, Signature},
+        signer::Signer,
+    };
+    use token_cli::{logs, multisig};
+    use tokens_interface::state::{Metadata, Role};
+
+    fn send(
+        arg_matches: &ArgMatches,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Signature, Box<dyn Error>> {
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            ixs,
+            Some(&signers[0].pubkey()),
+            signers,
+            rpc_client.get_latest_blockhash()?,
+        );
+        rpc_client
+            .send_and_confirm_transaction(&tx)
+            .map_err(token_cli::error::map_client_error)
+    }
+
+    fn get_mint(
+        arg_matches: &ArgMatches,
+    ) -> Result<solana_program::pubkey::Pubkey, Box<dyn Error>> {
+        let mint = arg_matches.get_one::<String>("mint").unwrap();
+        Ok(solana_program::pubkey::Pubkey::from_str(mint)?)
+    }
+    /// Metadata of the mint that amounts are read and shown with.
+    fn get_units(
+        arg_matches: &ArgMatches,
+        program_id: solana_program::pubkey::Pubkey,
+        mint: &solana_program::pubkey::Pubkey,
+    ) -> Result<Metadata, Box<dyn Error>> {
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        token_cli::read_units(program_id, mint, &rpc_client)
+    }
+    /// Treasury token account the transfer fee of the mint is withheld into.
+    fn get_treasury(
+        arg_matches: &ArgMatches,
+        program_id: solana_program::pubkey::Pubkey,
+        mint: &solana_program::pubkey::Pubkey,
+    ) -> Result<solana_program::pubkey::Pubkey, Box<dyn Error>> {
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        Ok(token_cli::read_mint(program_id, mint, &rpc_client)?.treasury)
+    }
+    /// Read a decimal amount such as `12.5` into the raw amount of the mint.
+    fn get_amount(
+        arg_matches: &ArgMatches,
+        name: &str,
+        units: &Metadata,
+    ) -> Result<u64, Box<dyn Error>> {
+        let value = arg_matches.get_one::<String>(name).unwrap();
+        Ok(units.parse_amount(value).ok_or(token_cli::error::Error(
+            "amount is not a number or has more decimals than the mint",
+        ))?)
+    }
+    pub fn airdrop(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let authority = arg_matches.get_one::<String>("Authority").unwrap();
+        let authority = Keypair::from_base58_string(authority);
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let dest = solana_program::pubkey::Pubkey::from_str(address)?;
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let pubkey = authority.pubkey();
+        let ix = token_cli::airdrop(program_id, &mint, &pubkey, &dest, amount);
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&pubkey),
+            &[&authority],
+            rpc_client.get_latest_blockhash()?,
+        );
+        let sig = rpc_client
+            .send_and_confirm_transaction(&tx)
+            .map_err(token_cli::error::map_client_error)?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn balance(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let pubkey = solana_program::pubkey::Pubkey::from_str(address)?;
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let state = token_cli::read_token_account(program_id, &mint, &pubkey, &rpc_client)?;
+        let units = token_cli::read_units(program_id, &mint, &rpc_client)?;
+        println!("{}", units.format_amount(state.balance));
+        if let Some(reason) = state.freeze_reason {
+            println!("account frozen, reason {reason}");
+        }
+        Ok(())
+    }
+    pub fn create_account(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let pubkey = address.pubkey();
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let ix = token_cli::init_account(program_id, &mint, &pubkey);
+        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&pubkey),
+            &[&address],
+            rpc_client.get_latest_blockhash()?,
+        );
+        let sig = rpc_client
+            .send_and_confirm_transaction(&tx)
+            .map_err(token_cli::error::map_client_error)?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn transfer(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let dest_address = arg_matches.get_one::<String>("Dest_Address").unwrap();
+        let dest = solana_program::pubkey::Pubkey::from_str(dest_address)?;
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let pubkey = address.pubkey();
+        let state = token_cli::read_mint(program_id, &mint, &rpc_client)?;
+        let fee = state.transfer_fee(amount);
+        println!(
+            "gross {} fee {} net {}",
+            units.format_amount(amount),
+            units.format_amount(fee),
+            units.format_amount(amount - fee)
+        );
+        let ix = token_cli::transfer(program_id, &mint, &pubkey, &dest, amount, &state.treasury);
+        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&pubkey),
+            &[&address],
+            rpc_client.get_latest_blockhash()?,
+        );
+        let sig = rpc_client
+            .send_and_confirm_transaction(&tx)
+            .map_err(token_cli::error::map_client_error)?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn create_log_book_account(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let payer = address.pubkey();
+        let (book, ix) = logs::init_log_book_account(&program_id, &payer);
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer),
+            &[address, book],
+            rpc_client.get_latest_blockhash()?,
+        );
+        let sig = rpc_client.send_transaction(&tx)?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn create_mint(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let supply_cap = arg_matches.get_one::<u64>("supply-cap").copied();
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let pubkey = address.pubkey();
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let (mint, ix) = match arg_matches.get_one::<String>("authority") {
+            Some(authority) => {
+                let authority = solana_program::pubkey::Pubkey::from_str(authority)?;
+                token_cli::init_mint_with_authority(program_id, &pubkey, &authority, supply_cap)
+            }
+            None => token_cli::init_mint(program_id, &pubkey, supply_cap),
+        };
+        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&pubkey),
+            &[&address, &mint],
+            rpc_client.get_latest_blockhash()?,
+        );
+        let sig = rpc_client
+            .send_and_confirm_transaction(&tx)
+            .map_err(token_cli::error::map_client_error)?;
+        println!("mint {}", mint.pubkey());
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn burn(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let pubkey = address.pubkey();
+        let ix = token_cli::burn(program_id, &mint, &pubkey, amount);
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&pubkey),
+            &[&address],
+            rpc_client.get_latest_blockhash()?,
+        );
+        let sig = rpc_client
+            .send_and_confirm_transaction(&tx)
+            .map_err(token_cli::error::map_client_error)?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn freeze(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let unlock_at = arg_matches.get_one::<i64>("unlock-at").copied();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let ix = token_cli::freeze(program_id, &mint, &address.pubkey(), amount, unlock_at);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn thaw(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let owner = match arg_matches.get_one::<String>("owner") {
+            Some(owner) => solana_program::pubkey::Pubkey::from_str(owner)?,
+            None => address.pubkey(),
+        };
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let ix = token_cli::thaw(program_id, &mint, &address.pubkey(), &owner, amount);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn freeze_account(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let owner = arg_matches.get_one::<String>("Owner").unwrap();
+        let owner = solana_program::pubkey::Pubkey::from_str(owner)?;
+        let reason = arg_matches.get_one::<u16>("reason").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let ix = token_cli::freeze_account(program_id, &mint, &address.pubkey(), &owner, *reason);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn thaw_account(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let owner = arg_matches.get_one::<String>("Owner").unwrap();
+        let owner = solana_program::pubkey::Pubkey::from_str(owner)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let ix = token_cli::thaw_account(program_id, &mint, &address.pubkey(), &owner);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn borrow(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let lender = arg_matches.get_one::<String>("Lender_Address").unwrap();
+        let lender = Keypair::from_base58_string(lender);
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let treasury = get_treasury(arg_matches, program_id, &mint)?;
+        let ix = token_cli::borrow(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &lender.pubkey(),
+            amount,
+            &treasury,
+        );
+        let sig = send(arg_matches, &[ix], &[&address, &lender])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn repay(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let lender = arg_matches.get_one::<String>("Lender_Address").unwrap();
+        let lender = solana_program::pubkey::Pubkey::from_str(lender)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let treasury = get_treasury(arg_matches, program_id, &mint)?;
+        let ix = token_cli::repay(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &lender,
+            amount,
+            &treasury,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn set_rate(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let borrower = arg_matches.get_one::<String>("Borrower_Address").unwrap();
+        let borrower = solana_program::pubkey::Pubkey::from_str(borrower)?;
+        let rate_bps = arg_matches.get_one::<u16>("Rate_Bps").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let ix = token_cli::set_rate(program_id, &mint, &address.pubkey(), &borrower, *rate_bps);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn credit(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = solana_program::pubkey::Pubkey::from_str(address)?;
+        let counterparty = arg_matches
+            .get_one::<String>("Counterparty_Address")
+            .unwrap();
+        let counterparty = solana_program::pubkey::Pubkey::from_str(counterparty)?;
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let log = logs::read_credit(
+            program_id,
+            tokens_interface::LOG_PROGRAM_ID,
+            &rpc_client,
+            &mint,
+            &address,
+            &counterparty,
+        )?;
+        let units = token_cli::read_units(program_id, &mint, &rpc_client)?;
+        println!("creditor {}", log.creditor);
+        println!("debtor {}", log.debtor);
+        println!("principal {}", units.format_amount(log.amount));
+        println!("interest {}", units.format_amount(log.interest));
+        println!("limit {}", units.format_amount(log.limit));
+        println!("collateral {}", units.format_amount(log.collateral));
+        println!("collateral ratio bps {}", log.ratio_bps);
+        println!("due at {}", log.due_at);
+        Ok(())
+    }
+    pub fn accrue(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let counterparty = arg_matches
+            .get_one::<String>("Counterparty_Address")
+            .unwrap();
+        let counterparty = solana_program::pubkey::Pubkey::from_str(counterparty)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let ix = token_cli::accrue(program_id, &mint, &address.pubkey(), &counterparty);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn set_credit_limit(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let borrower = arg_matches.get_one::<String>("Borrower_Address").unwrap();
+        let borrower = solana_program::pubkey::Pubkey::from_str(borrower)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let limit = get_amount(arg_matches, "Limit", &units)?;
+        let ix =
+            token_cli::set_credit_limit(program_id, &mint, &address.pubkey(), &borrower, limit);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn set_terms(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let borrower = arg_matches.get_one::<String>("Borrower_Address").unwrap();
+        let borrower = solana_program::pubkey::Pubkey::from_str(borrower)?;
+        let ratio_bps = arg_matches.get_one::<u16>("Ratio_Bps").unwrap();
+        let due_at = arg_matches.get_one::<i64>("due-at").copied();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let ix = token_cli::set_terms(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &borrower,
+            *ratio_bps,
+            due_at,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn lock_collateral(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let lender = arg_matches.get_one::<String>("Lender_Address").unwrap();
+        let lender = solana_program::pubkey::Pubkey::from_str(lender)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let ix = token_cli::lock_collateral(program_id, &mint, &address.pubkey(), &lender, amount);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn release_collateral(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let lender = arg_matches.get_one::<String>("Lender_Address").unwrap();
+        let lender = solana_program::pubkey::Pubkey::from_str(lender)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let ix =
+            token_cli::release_collateral(program_id, &mint, &address.pubkey(), &lender, amount);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn liquidate(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let borrower = arg_matches.get_one::<String>("Borrower_Address").unwrap();
+        let borrower = solana_program::pubkey::Pubkey::from_str(borrower)?;
+        let lender = arg_matches.get_one::<String>("Lender_Address").unwrap();
+        let lender = solana_program::pubkey::Pubkey::from_str(lender)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let treasury = get_treasury(arg_matches, program_id, &mint)?;
+        let ix = token_cli::liquidate(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &borrower,
+            &lender,
+            &treasury,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn approve(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let delegate = arg_matches.get_one::<String>("Delegate_Address").unwrap();
+        let delegate = solana_program::pubkey::Pubkey::from_str(delegate)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let ix = token_cli::approve(program_id, &mint, &address.pubkey(), &delegate, amount);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn revoke(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let ix = token_cli::revoke(program_id, &mint, &address.pubkey());
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn transfer_from(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let owner = arg_matches.get_one::<String>("Owner_Address").unwrap();
+        let owner = solana_program::pubkey::Pubkey::from_str(owner)?;
+        let dest = arg_matches.get_one::<String>("Dest_Address").unwrap();
+        let dest = solana_program::pubkey::Pubkey::from_str(dest)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let treasury = token_cli::read_mint(program_id, &mint, &rpc_client)?.treasury;
+        let ix = token_cli::transfer_from(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &owner,
+            &dest,
+            amount,
+            &treasury,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn create_multisig(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let m = arg_matches.get_one::<u8>("M").unwrap();
+        let signers = arg_matches
+            .get_many::<String>("Signers")
+            .unwrap()
+            .map(|signer| solana_program::pubkey::Pubkey::from_str(signer))
+            .collect::<Result<Vec<_>, _>>()?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let (multisig, ixs) =
+            token_cli::init_multisig(program_id, &mint, &address.pubkey(), *m, &signers);
+        let sig = send(arg_matches, &ixs, &[&address])?;
+        println!("multisig {}", multisig);
+        println!("{}", sig);
+        Ok(())
+    }
+    /// Print the unsigned transaction of `ix`, built for the multisig, for its co-signers.
+    fn print_multisig_transaction(
+        arg_matches: &ArgMatches,
+        ix: Instruction,
+    ) -> Result<(), Box<dyn Error>> {
+        let signers = arg_matches
+            .get_many::<String>("signer")
+            .unwrap()
+            .map(|signer| solana_program::pubkey::Pubkey::from_str(signer))
+            .collect::<Result<Vec<_>, _>>()?;
+        let fee_payer = match arg_matches.get_one::<String>("fee-payer") {
+            Some(fee_payer) => solana_program::pubkey::Pubkey::from_str(fee_payer)?,
+            None => signers[0],
+        };
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let ix = token_cli::with_multisig(ix, &signers);
+        let tx =
+            multisig::unsigned_transaction(&[ix], &fee_payer, rpc_client.get_latest_blockhash()?);
+        println!("{}", multisig::encode_transaction(&tx)?);
+        Ok(())
+    }
+    pub fn multisig_transfer(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Multisig_Address").unwrap();
+        let address = solana_program::pubkey::Pubkey::from_str(address)?;
+        let dest = arg_matches.get_one::<String>("Dest_Address").unwrap();
+        let dest = solana_program::pubkey::Pubkey::from_str(dest)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let treasury = token_cli::read_mint(program_id, &mint, &rpc_client)?.treasury;
+        let ix = token_cli::transfer(program_id, &mint, &address, &dest, amount, &treasury);
+        print_multisig_transaction(arg_matches, ix)
+    }
+    pub fn multisig_airdrop(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let authority = arg_matches.get_one::<String>("Multisig_Address").unwrap();
+        let authority = solana_program::pubkey::Pubkey::from_str(authority)?;
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let dest = solana_program::pubkey::Pubkey::from_str(address)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let ix = token_cli::airdrop(program_id, &mint, &authority, &dest, amount);
+        print_multisig_transaction(arg_matches, ix)
+    }
+    pub fn sign_transaction(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let tx = arg_matches.get_one::<String>("Transaction").unwrap();
+        let mut tx = multisig::decode_transaction(tx)?;
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        multisig::partial_sign(&mut tx, &address)?;
+        println!("{}", multisig::encode_transaction(&tx)?);
+        for key in multisig::missing_signers(&tx) {
+            eprintln!("missing signature of {}", key);
+        }
+        Ok(())
+    }
+    pub fn send_transaction(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let tx = arg_matches.get_one::<String>("Transaction").unwrap();
+        let tx = multisig::decode_transaction(tx)?;
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let sig = rpc_client
+            .send_and_confirm_transaction(&tx)
+            .map_err(token_cli::error::map_client_error)?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn close_account(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let destination = match arg_matches.get_one::<String>("destination") {
+            Some(destination) => solana_program::pubkey::Pubkey::from_str(destination)?,
+            None => address.pubkey(),
+        };
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let counterparties = arg_matches
+            .get_many::<String>("counterparty")
+            .unwrap_or_default()
+            .map(|counterparty| solana_program::pubkey::Pubkey::from_str(counterparty))
+            .collect::<Result<Vec<_>, _>>()?;
+        let ix = token_cli::close_account(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &destination,
+            &counterparties,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn transfer_batch(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let units = token_cli::read_units(program_id, &mint, &rpc_client)?;
+        let mut payments = Vec::new();
+        for payment in arg_matches.get_many::<String>("Payments").unwrap() {
+            let (dest, amount) = payment.split_once(':').ok_or(token_cli::error::Error(
+                "payment must be Dest_Address:Amount",
+            ))?;
+            let amount = units.parse_amount(amount).ok_or(token_cli::error::Error(
+                "amount is not a number or has more decimals than the mint",
+            ))?;
+            payments.push((solana_program::pubkey::Pubkey::from_str(dest)?, amount));
+        }
+        let treasury = token_cli::read_mint(program_id, &mint, &rpc_client)?.treasury;
+        for ix in
+            token_cli::transfer_batch(program_id, &mint, &address.pubkey(), &payments, &treasury)
+        {
+            let sig = send(arg_matches, &[ix], &[&address])?;
+            println!("{}", sig);
+        }
+        Ok(())
+    }
+    /// Decode 32 bytes written in base58.
+    fn parse_bytes32(arg_matches: &ArgMatches, name: &str) -> Result<[u8; 32], Box<dyn Error>> {
+        let value = arg_matches.get_one::<String>(name).unwrap();
+        let raw = solana_sdk::bs58::decode(value).into_vec()?;
+        Ok(raw
+            .try_into()
+            .map_err(|_| token_cli::error::Error("value must be 32 bytes"))?)
+    }
+    pub fn htlc_lock(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let recipient = arg_matches.get_one::<String>("Recipient_Address").unwrap();
+        let recipient = solana_program::pubkey::Pubkey::from_str(recipient)?;
+        let hash = parse_bytes32(arg_matches, "Hash")?;
+        let expiry_slot = arg_matches.get_one::<u64>("Expiry_Slot").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let amount = get_amount(arg_matches, "Amount", &units)?;
+        let ix = token_cli::lock_htlc(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &recipient,
+            amount,
+            hash,
+            *expiry_slot,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn htlc_claim(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let sender = arg_matches.get_one::<String>("Sender_Address").unwrap();
+        let sender = solana_program::pubkey::Pubkey::from_str(sender)?;
+        let recipient = arg_matches.get_one::<String>("Recipient_Address").unwrap();
+        let recipient = solana_program::pubkey::Pubkey::from_str(recipient)?;
+        let preimage = parse_bytes32(arg_matches, "Preimage")?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let treasury = get_treasury(arg_matches, program_id, &mint)?;
+        let ix = token_cli::claim_htlc(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &sender,
+            &recipient,
+            preimage,
+            &treasury,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn htlc_reclaim(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let recipient = arg_matches.get_one::<String>("Recipient_Address").unwrap();
+        let recipient = solana_program::pubkey::Pubkey::from_str(recipient)?;
+        let hash = parse_bytes32(arg_matches, "Hash")?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let ix = token_cli::reclaim_htlc(program_id, &mint, &address.pubkey(), &recipient, &hash);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn vesting_create(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let beneficiary = arg_matches
+            .get_one::<String>("Beneficiary_Address")
+            .unwrap();
+        let beneficiary = solana_program::pubkey::Pubkey::from_str(beneficiary)?;
+        let start = arg_matches.get_one::<i64>("Start").unwrap();
+        let cliff = arg_matches.get_one::<i64>("Cliff").unwrap();
+        let end = arg_matches.get_one::<i64>("End").unwrap();
+        let revocable = arg_matches.get_flag("revocable");
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let total = get_amount(arg_matches, "Total", &units)?;
+        let ix = token_cli::create_vesting(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &beneficiary,
+            total,
+            (*start, *cliff, *end),
+            revocable,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn vesting_release(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let grantor = arg_matches.get_one::<String>("Grantor_Address").unwrap();
+        let grantor = solana_program::pubkey::Pubkey::from_str(grantor)?;
+        let beneficiary = arg_matches
+            .get_one::<String>("Beneficiary_Address")
+            .unwrap();
+        let beneficiary = solana_program::pubkey::Pubkey::from_str(beneficiary)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let treasury = get_treasury(arg_matches, program_id, &mint)?;
+        let ix = token_cli::release(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &grantor,
+            &beneficiary,
+            &treasury,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn vesting_revoke(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let beneficiary = arg_matches
+            .get_one::<String>("Beneficiary_Address")
+            .unwrap();
+        let beneficiary = solana_program::pubkey::Pubkey::from_str(beneficiary)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let treasury = get_treasury(arg_matches, program_id, &mint)?;
+        let ix = token_cli::revoke_vesting(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &beneficiary,
+            &treasury,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn vesting(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let grantor = arg_matches.get_one::<String>("Grantor_Address").unwrap();
+        let grantor = solana_program::pubkey::Pubkey::from_str(grantor)?;
+        let beneficiary = arg_matches
+            .get_one::<String>("Beneficiary_Address")
+            .unwrap();
+        let beneficiary = solana_program::pubkey::Pubkey::from_str(beneficiary)?;
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let vesting =
+            token_cli::read_vesting(program_id, &mint, &rpc_client, &grantor, &beneficiary)?;
+        let clock: solana_sdk::clock::Clock =
+            bincode::deserialize(&rpc_client.get_account_data(&solana_sdk::sysvar::clock::id())?)?;
+        let units = token_cli::read_units(program_id, &mint, &rpc_client)?;
+        println!("total {}", units.format_amount(vesting.total));
+        println!("released {}", units.format_amount(vesting.released));
+        println!(
+            "vested {}",
+            units.format_amount(vesting.vested(clock.unix_timestamp))
+        );
+        println!("start {}", vesting.start);
+        println!("cliff {}", vesting.cliff);
+        println!("end {}", vesting.end);
+        println!("revocable {}", vesting.revocable);
+        Ok(())
+    }
+    pub fn stream_create(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let recipient = arg_matches.get_one::<String>("Recipient_Address").unwrap();
+        let recipient = solana_program::pubkey::Pubkey::from_str(recipient)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let deposit = get_amount(arg_matches, "Deposit", &units)?;
+        let rate = get_amount(arg_matches, "Rate", &units)?;
+        let ix = token_cli::create_stream(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &recipient,
+            deposit,
+            rate,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn stream_withdraw(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let sender = arg_matches.get_one::<String>("Sender_Address").unwrap();
+        let sender = solana_program::pubkey::Pubkey::from_str(sender)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let treasury = get_treasury(arg_matches, program_id, &mint)?;
+        let ix = token_cli::withdraw(program_id, &mint, &address.pubkey(), &sender, &treasury);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn stream_cancel(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let sender = arg_matches.get_one::<String>("Sender_Address").unwrap();
+        let sender = solana_program::pubkey::Pubkey::from_str(sender)?;
+        let recipient = arg_matches.get_one::<String>("Recipient_Address").unwrap();
+        let recipient = solana_program::pubkey::Pubkey::from_str(recipient)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let treasury = get_treasury(arg_matches, program_id, &mint)?;
+        let ix = token_cli::cancel_stream(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &sender,
+            &recipient,
+            &treasury,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn stream(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let sender = arg_matches.get_one::<String>("Sender_Address").unwrap();
+        let sender = solana_program::pubkey::Pubkey::from_str(sender)?;
+        let recipient = arg_matches.get_one::<String>("Recipient_Address").unwrap();
+        let recipient = solana_program::pubkey::Pubkey::from_str(recipient)?;
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let stream = token_cli::read_stream(program_id, &mint, &rpc_client, &sender, &recipient)?;
+        let clock: solana_sdk::clock::Clock =
+            bincode::deserialize(&rpc_client.get_account_data(&solana_sdk::sysvar::clock::id())?)?;
+        let units = token_cli::read_units(program_id, &mint, &rpc_client)?;
+        println!("deposit {}", units.format_amount(stream.deposit));
+        println!("withdrawn {}", units.format_amount(stream.withdrawn));
+        println!("rate {}/s", units.format_amount(stream.rate));
+        println!("start {}", stream.start);
+        println!(
+            "streamed {}",
+            units.format_amount(stream.streamed(clock.unix_timestamp))
+        );
+        println!(
+            "available {}",
+            units.format_amount(stream.available(clock.unix_timestamp))
+        );
+        Ok(())
+    }
+    pub fn set_transfer_fee(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let fee_bps = arg_matches.get_one::<u16>("Fee_Bps").unwrap();
+        let treasury = arg_matches.get_one::<String>("Treasury_Address").unwrap();
+        let treasury = solana_program::pubkey::Pubkey::from_str(treasury)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let units = get_units(arg_matches, program_id, &mint)?;
+        let max_fee = get_amount(arg_matches, "Max_Fee", &units)?;
+        let ix = token_cli::set_transfer_fee(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            *fee_bps,
+            max_fee,
+            &treasury,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn set_config_roles(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let enabled = arg_matches.get_one::<bool>("Enabled").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let ix = token_cli::set_config_roles(program_id, &mint, &address.pubkey(), *enabled);
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn create_metadata(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let decimals = arg_matches.get_one::<u8>("Decimals").unwrap();
+        let name = arg_matches.get_one::<String>("Name").unwrap();
+        let symbol = arg_matches.get_one::<String>("Symbol").unwrap();
+        let uri = arg_matches.get_one::<String>("uri").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let ix = token_cli::init_metadata(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            *decimals,
+            name,
+            symbol,
+            uri,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn update_metadata(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        //keep what is not given
+        let metadata = token_cli::read_metadata(program_id, &mint, &rpc_client)?;
+        let name = arg_matches
+            .get_one::<String>("name")
+            .unwrap_or(&metadata.name);
+        let symbol = arg_matches
+            .get_one::<String>("symbol")
+            .unwrap_or(&metadata.symbol);
+        let uri = arg_matches
+            .get_one::<String>("uri")
+            .unwrap_or(&metadata.uri);
+        let authority = match arg_matches.get_one::<String>("authority") {
+            Some(authority) => solana_program::pubkey::Pubkey::from_str(authority)?,
+            None => metadata.update_authority,
+        };
+        let ix = token_cli::update_metadata(
+            program_id,
+            &mint,
+            &address.pubkey(),
+            &authority,
+            name,
+            symbol,
+            uri,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn metadata(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let metadata = token_cli::read_metadata(program_id, &mint, &rpc_client)?;
+        let supply = token_cli::read_mint(program_id, &mint, &rpc_client)?.supply;
+        println!("name {}", metadata.name);
+        println!("symbol {}", metadata.symbol);
+        println!("decimals {}", metadata.decimals);
+        println!("uri {}", metadata.uri);
+        println!("update authority {}", metadata.update_authority);
+        println!("supply {}", metadata.format_amount(supply));
+        Ok(())
+    }
+    pub fn migrate(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let batch_size = arg_matches.get_one::<usize>("batch-size").unwrap();
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let mint = get_mint(arg_matches)?;
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let mut ixs = Vec::new();
+        for account in token_cli::migrate::find_legacy_accounts(program_id, &rpc_client)? {
+            match token_cli::migrate::legacy_owner(program_id, &rpc_client, &account)? {
+                Some(owner) => ixs.push(token_cli::migrate(
+                    program_id,
+                    &mint,
+                    &address.pubkey(),
+                    &address.pubkey(),
+                    &owner,
+                )),
+                None => eprintln!("no owner found for legacy account {}", account),
+            }
+        }
+        println!("{} legacy accounts to migrate", ixs.len());
+        for batch in ixs.chunks((*batch_size).max(1)) {
+            let sig = send(arg_matches, batch, &[&address])?;
+            println!("{}", sig);
+        }
+        Ok(())
+    }
+    pub fn events(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let signature = arg_matches.get_one::<String>("Signature").unwrap();
+        let signature = Signature::from_str(signature)?;
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let program_ids = [program_id, tokens_interface::LOG_PROGRAM_ID];
+        for event in token_cli::events::read_events(&rpc_client, &program_ids, &signature)? {
+            println!("{:?}", event);
+        }
+        Ok(())
+    }
+    pub fn admin_init(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let admin = arg_matches
+            .get_one::<String>("admin")
+            .map(|admin| solana_program::pubkey::Pubkey::from_str(admin))
+            .transpose()?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let ix = token_cli::init_config(program_id, &address.pubkey(), admin.as_ref());
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn admin_pause(arg_matches: &ArgMatches, paused: bool) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let ix = if paused {
+            token_cli::pause(program_id, &address.pubkey())
+        } else {
+            token_cli::unpause(program_id, &address.pubkey())
+        };
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn admin_status(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let config = token_cli::read_config(program_id, &rpc_client)?;
+        println!("admin {}", config.admin);
+        println!("paused {}", config.paused);
+        Ok(())
+    }
+    fn get_role(arg_matches: &ArgMatches) -> Role {
+        let name = arg_matches.get_one::<String>("Role").unwrap();
+        *Role::ALL.iter().find(|role| role.name() == name).unwrap()
+    }
+    pub fn roles_list(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let rpc_url = arg_matches.get_one::<String>("url").unwrap();
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let rpc_client = solana_client::rpc_client::RpcClient::new(rpc_url);
+        let config = token_cli::read_config(program_id, &rpc_client)?;
+        println!("admin {}", config.admin);
+        for role in Role::ALL {
+            match config.role(role) {
+                Some(holder) => println!("{} {}", role.name(), holder),
+                None => println!("{} unassigned", role.name()),
+            }
+        }
+        Ok(())
+    }
+    pub fn roles_grant(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let holder = arg_matches.get_one::<String>("Holder").unwrap();
+        let holder = solana_program::pubkey::Pubkey::from_str(holder)?;
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let ix = token_cli::grant_role(
+            program_id,
+            &address.pubkey(),
+            get_role(arg_matches),
+            &holder,
+        );
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
+        Ok(())
+    }
+    pub fn roles_revoke(arg_matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let address = arg_matches.get_one::<String>("Address").unwrap();
+        let address = Keypair::from_base58_string(address);
+        let netype = arg_matches.get_one::<String>("network").unwrap();
+        let nt = netype.as_str();
+        let program_id = get_program_id!(nt);
+        let ix = token_cli::revoke_role(program_id, &address.pubkey(), get_role(arg_matches));
+        let sig = send(arg_matches, &[ix], &[&address])?;
+        println!("{}", sig);
         Ok(())
     }
 }
@@ -0,0 +1,82 @@
+//! Legacy token accounts are the 80 byte accounts of the first program version.
+//! Their data holds no owner, so the owner is recovered from the transaction
+//! that created the account, signed by the owner whose key seeds the pda.
+use std::error::Error;
+use std::str::FromStr;
+
+use solana_client::rpc_client::{self, GetConfirmedSignaturesForAddress2Config};
+use solana_client::rpc_config::{RpcProgramAccountsConfig, RpcTransactionConfig};
+use solana_client::rpc_filter::RpcFilterType;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::UiTransactionEncoding;
+use tokens_interface::instruction::get_legacy_token_account;
+use tokens_interface::state::LegacyTokenAccount;
+
+/// Addresses of every legacy token account of the program.
+pub fn find_legacy_accounts(
+    program_id: Pubkey,
+    c: &rpc_client::RpcClient,
+) -> Result<Vec<Pubkey>, Box<dyn Error>> {
+    let accounts = c.get_program_accounts_with_config(
+        &program_id,
+        RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::DataSize(
+                LegacyTokenAccount::LEN as u64,
+            )]),
+            ..Default::default()
+        },
+    )?;
+    Ok(accounts.into_iter().map(|(key, _)| key).collect())
+}
+
+/// Owner of a legacy token account, the key of its creation transaction that
+/// derives the account address. `None` when no key does.
+pub fn legacy_owner(
+    program_id: Pubkey,
+    c: &rpc_client::RpcClient,
+    account: &Pubkey,
+) -> Result<Option<Pubkey>, Box<dyn Error>> {
+    //signatures come newest first, page back to the oldest one
+    let mut oldest = None;
+    loop {
+        let page = c.get_signatures_for_address_with_config(
+            account,
+            GetConfirmedSignaturesForAddress2Config {
+                before: oldest,
+                ..Default::default()
+            },
+        )?;
+        match page.last() {
+            Some(status) => oldest = Some(Signature::from_str(&status.signature)?),
+            None => break,
+        }
+        if page.len() < 1000 {
+            break;
+        }
+    }
+    let Some(signature) = oldest else {
+        return Ok(None);
+    };
+    let tx = c.get_transaction_with_config(
+        &signature,
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            max_supported_transaction_version: Some(0),
+            ..Default::default()
+        },
+    )?;
+    let tx = tx
+        .transaction
+        .transaction
+        .decode()
+        .ok_or(crate::error::Error(
+            "creation transaction can not be decoded",
+        ))?;
+    Ok(tx
+        .message
+        .static_account_keys()
+        .iter()
+        .find(|key| get_legacy_token_account(key, &program_id).0 == *account)
+        .copied())
+}
@@ -0,0 +1,48 @@
+//! Transactions of a multisig are built once, passed around as base58 text and
+//! signed by each co-signer until enough signatures are present.
+use std::error::Error;
+
+use solana_program::{hash::Hash, instruction::Instruction, pubkey::Pubkey};
+use solana_sdk::{bs58, signature::Keypair, signer::Signer, transaction::Transaction};
+
+pub fn unsigned_transaction(
+    ixs: &[Instruction],
+    fee_payer: &Pubkey,
+    recent_blockhash: Hash,
+) -> Transaction {
+    let mut tx = Transaction::new_with_payer(ixs, Some(fee_payer));
+    tx.message.recent_blockhash = recent_blockhash;
+    tx
+}
+
+/// Add the signature of `signer`, the other signatures are kept.
+pub fn partial_sign(tx: &mut Transaction, signer: &Keypair) -> Result<(), Box<dyn Error>> {
+    if !tx.message.signer_keys().contains(&&signer.pubkey()) {
+        return Err(Box::new(crate::error::Error(
+            "keypair is not a signer of the transaction",
+        )));
+    }
+    let recent_blockhash = tx.message.recent_blockhash;
+    tx.try_partial_sign(&[signer], recent_blockhash)?;
+    Ok(())
+}
+
+/// Signer keys whose signature is still missing.
+pub fn missing_signers(tx: &Transaction) -> Vec<Pubkey> {
+    tx.message
+        .signer_keys()
+        .into_iter()
+        .zip(&tx.signatures)
+        .filter(|(_, signature)| **signature == Default::default())
+        .map(|(key, _)| *key)
+        .collect()
+}
+
+pub fn encode_transaction(tx: &Transaction) -> Result<String, Box<dyn Error>> {
+    Ok(bs58::encode(bincode::serialize(tx)?).into_string())
+}
+
+pub fn decode_transaction(encoded: &str) -> Result<Transaction, Box<dyn Error>> {
+    let raw = bs58::decode(encoded).into_vec()?;
+    Ok(bincode::deserialize(&raw)?)
+}
@@ -0,0 +1,44 @@
+//! Program ids of the cluster to deploy to, read from the environment at build
+//! time so that every deployment names the programs it actually deployed.
+use std::{env, fs, path::Path};
+
+/// Tokens program deployed to testnet.
+const TESTNET_TOKENS_PROGRAM_ID: &str = "9zuZUTkJBdrp6zZ6uEDjVjUHZT18rKcjchdqQYqyiy4C";
+/// Stands in for a log program that is not deployed, every call to it fails.
+const NO_LOG_PROGRAM: &str = "11111111111111111111111111111111";
+
+fn program_id(name: &str, default: Option<&str>) -> String {
+    println!("cargo:rerun-if-env-changed={}", name);
+    let id = match (env::var(name), default) {
+        (Ok(id), _) => id,
+        (Err(_), Some(id)) => id.to_string(),
+        (Err(_), None) => panic!("set {} to the id of the deployed program", name),
+    };
+    match bs58::decode(&id).into_vec() {
+        Ok(bytes) if bytes.len() == 32 => id,
+        _ => panic!("{} is not a program id: {}", name, id),
+    }
+}
+
+fn main() {
+    let testnet = env::var_os("CARGO_FEATURE_TESTNET").is_some();
+    //host builds for tests may leave the log program out, a program built to
+    //deploy may not
+    let on_chain = env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "solana");
+    let tokens = program_id(
+        "TOKENS_PROGRAM_ID",
+        testnet.then_some(TESTNET_TOKENS_PROGRAM_ID),
+    );
+    let log = program_id("LOG_PROGRAM_ID", (!on_chain).then_some(NO_LOG_PROGRAM));
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("program_ids.rs");
+    fs::write(
+        out,
+        format!(
+            "/// Log program the tokens program records credit in, `LOG_PROGRAM_ID` at build time.\n\
+             pub static LOG_PROGRAM_ID: Pubkey = solana_program::pubkey!(\"{log}\");\n\
+             /// Tokens program, `TOKENS_PROGRAM_ID` at build time or the testnet deployment.\n\
+             pub static TOKENS_PROGRAM_ID: Pubkey = solana_program::pubkey!(\"{tokens}\");\n"
+        ),
+    )
+    .unwrap();
+}
@@ -0,0 +1,172 @@
+//! Errors of the tokens program and the log program, returned as
+//! `ProgramError::Custom` with stable codes. Log program codes start at 256 so
+//! a client can tell both apart from the code alone.
+use num_derive::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum TokenError {
+    #[error("source and destination must be different accounts")]
+    SameAccount = 0,
+    #[error("balance is not enough")]
+    InsufficientFunds = 1,
+    #[error("signer is not the token account owner")]
+    NotOwner = 2,
+    #[error("signer is not the mint authority or the role holder")]
+    NotMintAuthority = 3,
+    #[error("supply cap is exceeded")]
+    SupplyCapExceeded = 4,
+    #[error("lender has not set a credit limit")]
+    NoCreditLine = 5,
+    #[error("credit limit is exceeded")]
+    CreditLimitExceeded = 6,
+    #[error("collateral does not cover the debt")]
+    Undercollateralized = 7,
+    #[error("lender balance does not cover the borrow")]
+    InsufficientCreditValue = 8,
+    #[error("frozen balance is still locked")]
+    FrozenLocked = 9,
+    #[error("signer is neither the owner nor the freezer")]
+    NotOwnerOrAuthority = 10,
+    #[error("frozen balance is not enough")]
+    InsufficientFrozen = 11,
+    #[error("repay amount is more than owed")]
+    RepayExceedsDebt = 12,
+    #[error("collateral is still needed for the debt")]
+    CollateralNeeded = 13,
+    #[error("borrower owes the lender nothing")]
+    NothingOwed = 14,
+    #[error("debt is neither past due nor under-collateralized")]
+    NotLiquidatable = 15,
+    #[error("borrower has no collateral to seize")]
+    NoCollateral = 16,
+    #[error("signer is not the delegate of the token account")]
+    NotDelegate = 17,
+    #[error("allowance is not enough")]
+    InsufficientAllowance = 18,
+    #[error("multisig needs 1 <= m <= n <= 11 signers")]
+    InvalidMultisig = 19,
+    #[error("not enough multisig signatures")]
+    NotEnoughSigners = 20,
+    #[error("only an empty account without credit can be closed")]
+    AccountNotEmpty = 21,
+    #[error("preimage does not match the hash lock")]
+    HashMismatch = 22,
+    #[error("escrow has expired")]
+    EscrowExpired = 23,
+    #[error("escrow is still locked")]
+    EscrowLocked = 24,
+    #[error("accounts do not match the escrow, grant or stream")]
+    AccountMismatch = 25,
+    #[error("signer is not the escrow sender")]
+    NotSender = 26,
+    #[error("signer is not the grantor")]
+    NotGrantor = 27,
+    #[error("token account belongs to someone else")]
+    WrongTokenAccount = 28,
+    #[error("vesting needs start <= cliff <= end")]
+    InvalidSchedule = 29,
+    #[error("nothing more is vested yet")]
+    NothingVested = 30,
+    #[error("vesting grant is not revocable")]
+    NotRevocable = 31,
+    #[error("stream rate must be positive")]
+    ZeroRate = 32,
+    #[error("nothing more is streamed yet")]
+    NothingStreamed = 33,
+    #[error("signer is neither the sender nor the recipient")]
+    NotStreamParty = 34,
+    #[error("fee can not be more than 10000 basis points")]
+    FeeTooHigh = 35,
+    #[error("treasury does not match the mint")]
+    TreasuryMismatch = 36,
+    #[error("no credit is recorded between the pair")]
+    NoCreditRecorded = 37,
+    #[error("collateral is not enough")]
+    InsufficientCollateral = 38,
+    #[error("program is paused")]
+    Paused = 39,
+    #[error("signer is not the admin or the role holder")]
+    NotAdmin = 40,
+    #[error("account is frozen by the freeze authority")]
+    AccountFrozen = 41,
+    #[error("account is not frozen")]
+    AccountNotFrozen = 42,
+    #[error("token account belongs to another mint")]
+    MintMismatch = 43,
+    #[error("metadata field is too long or decimals are above 19")]
+    InvalidMetadata = 44,
+    #[error("signer is not the metadata update authority")]
+    NotUpdateAuthority = 45,
+    #[error("account is not a legacy 80 byte token account")]
+    NotLegacyAccount = 46,
+    #[error("multisig signer is listed twice")]
+    DuplicateSigner = 47,
+    #[error("escrow expiry slot has already passed")]
+    InvalidExpiry = 48,
+    #[error("signer is not the program upgrade authority")]
+    NotUpgradeAuthority = 49,
+    #[error("legacy account has borrowed or lent tokens outstanding")]
+    OpenCredit = 50,
+}
+
+impl From<TokenError> for ProgramError {
+    fn from(e: TokenError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for TokenError {
+    fn type_of() -> &'static str {
+        "TokenError"
+    }
+}
+
+impl PrintProgramError for TokenError {
+    fn print<E>(&self) {
+        msg!("{}", self);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum LogError {
+    #[error("interest can only be paid by the debtor")]
+    InterestFromCreditor = 256,
+    #[error("interest must be paid before the credit is reversed")]
+    InterestOutstanding = 257,
+    #[error("interest paid is more than accrued")]
+    InterestExceeded = 258,
+    #[error("terms can only be loosened while the debt is outstanding")]
+    TermsTightened = 259,
+    #[error("book is owned by another program")]
+    BookOwnedElsewhere = 260,
+    #[error("log can only be written by the tokens program")]
+    NotTokensProgram = 261,
+    #[error("a1 must be greater than a2")]
+    UnorderedPair = 262,
+    #[error("log account does not belong to the pair")]
+    PdaMismatch = 263,
+}
+
+impl From<LogError> for ProgramError {
+    fn from(e: LogError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for LogError {
+    fn type_of() -> &'static str {
+        "LogError"
+    }
+}
+
+impl PrintProgramError for LogError {
+    fn print<E>(&self) {
+        msg!("{}", self);
+    }
+}
@@ -0,0 +1,1099 @@
+//! Events the tokens program and the log program emit through `sol_log_data`.
+//!
+//! Every event is one data field laid out as the version byte, the event tag
+//! and the fields in big endian. Tokens program events reuse the tag of the
+//! instruction that emits them, log program events start at 64.
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::state::Role;
+use crate::{expect_end, unpack_bytes32, unpack_u16, unpack_u64, unpack_u8};
+
+pub const EVENT_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    InitAccount {
+        account: Pubkey,
+        owner: Pubkey,
+    },
+    /// Emitted by `Transfer`, `TransferFrom` and once per destination of
+    /// `TransferBatch`. `amount` left the source, `fee` went to the treasury.
+    Transfer {
+        from: Pubkey,
+        to: Pubkey,
+        amount: u64,
+        fee: u64,
+    },
+    MintTo {
+        account: Pubkey,
+        amount: u64,
+        supply: u64,
+    },
+    Freeze {
+        account: Pubkey,
+        amount: u64,
+        unlock_at: i64,
+    },
+    Borrow {
+        borrower: Pubkey,
+        lender: Pubkey,
+        amount: u64,
+    },
+    InitMint {
+        mint: Pubkey,
+        authority: Pubkey,
+    },
+    Burn {
+        account: Pubkey,
+        amount: u64,
+        supply: u64,
+    },
+    Thaw {
+        account: Pubkey,
+        amount: u64,
+    },
+    Repay {
+        debtor: Pubkey,
+        creditor: Pubkey,
+        principal: u64,
+        interest: u64,
+    },
+    SetRate {
+        lender: Pubkey,
+        borrower: Pubkey,
+        rate_bps: u16,
+    },
+    SetCreditLimit {
+        lender: Pubkey,
+        borrower: Pubkey,
+        limit: u64,
+    },
+    /// `due_at` is 0 when the debt has no due date.
+    SetTerms {
+        lender: Pubkey,
+        borrower: Pubkey,
+        ratio_bps: u16,
+        due_at: i64,
+    },
+    LockCollateral {
+        borrower: Pubkey,
+        lender: Pubkey,
+        amount: u64,
+    },
+    ReleaseCollateral {
+        borrower: Pubkey,
+        lender: Pubkey,
+        amount: u64,
+    },
+    Liquidate {
+        borrower: Pubkey,
+        lender: Pubkey,
+        seized: u64,
+    },
+    Approve {
+        account: Pubkey,
+        delegate: Pubkey,
+        amount: u64,
+    },
+    Revoke {
+        account: Pubkey,
+    },
+    InitMultisig {
+        multisig: Pubkey,
+        m: u8,
+        n: u8,
+    },
+    CloseAccount {
+        account: Pubkey,
+        destination: Pubkey,
+    },
+    LockHtlc {
+        escrow: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        expiry_slot: u64,
+    },
+    ClaimHtlc {
+        escrow: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+    },
+    ReclaimHtlc {
+        escrow: Pubkey,
+        sender: Pubkey,
+        amount: u64,
+    },
+    CreateVesting {
+        vesting: Pubkey,
+        beneficiary: Pubkey,
+        total: u64,
+    },
+    Release {
+        vesting: Pubkey,
+        amount: u64,
+    },
+    /// `vested` went to the beneficiary, `returned` back to the grantor.
+    RevokeVesting {
+        vesting: Pubkey,
+        vested: u64,
+        returned: u64,
+    },
+    CreateStream {
+        stream: Pubkey,
+        recipient: Pubkey,
+        deposit: u64,
+        rate: u64,
+    },
+    Withdraw {
+        stream: Pubkey,
+        amount: u64,
+    },
+    /// `paid` went to the recipient, `refunded` back to the sender.
+    CancelStream {
+        stream: Pubkey,
+        paid: u64,
+        refunded: u64,
+    },
+    SetTransferFee {
+        fee_bps: u16,
+        max_fee: u64,
+        treasury: Pubkey,
+    },
+    InitConfig {
+        config: Pubkey,
+        admin: Pubkey,
+    },
+    Pause {
+        pauser: Pubkey,
+    },
+    Unpause {
+        pauser: Pubkey,
+    },
+    GrantRole {
+        role: Role,
+        holder: Pubkey,
+    },
+    RevokeRole {
+        role: Role,
+    },
+    FreezeAccount {
+        account: Pubkey,
+        reason: u16,
+    },
+    ThawAccount {
+        account: Pubkey,
+    },
+    InitMetadata {
+        metadata: Pubkey,
+        mint: Pubkey,
+        decimals: u8,
+    },
+    /// The strings changed, read them from the metadata account.
+    UpdateMetadata {
+        metadata: Pubkey,
+        update_authority: Pubkey,
+    },
+    /// The legacy account was closed, its balances moved to `account`.
+    Migrate {
+        legacy: Pubkey,
+        account: Pubkey,
+        owner: Pubkey,
+    },
+    SetConfigRoles {
+        mint: Pubkey,
+        enabled: bool,
+    },
+    /// Log pda of a pair created by the log program.
+    InitLog {
+        log: Pubkey,
+    },
+    InitBook {
+        book: Pubkey,
+    },
+    /// Net credit of a pair after the log program wrote it.
+    LogUpdated {
+        log: Pubkey,
+        direction: u8,
+        amount: u64,
+        interest: u64,
+    },
+}
+
+impl Event {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (version, rest) = unpack_u8(input)?;
+        if version != EVENT_VERSION {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (tag, rest) = unpack_u8(rest)?;
+        let (event, rest) = match tag {
+            0 => {
+                let (account, rest) = unpack_pubkey(rest)?;
+                let (owner, rest) = unpack_pubkey(rest)?;
+                (Self::InitAccount { account, owner }, rest)
+            }
+            1 => {
+                let (from, rest) = unpack_pubkey(rest)?;
+                let (to, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                let (fee, rest) = unpack_u64(rest)?;
+                (
+                    Self::Transfer {
+                        from,
+                        to,
+                        amount,
+                        fee,
+                    },
+                    rest,
+                )
+            }
+            2 => {
+                let (account, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                let (supply, rest) = unpack_u64(rest)?;
+                (
+                    Self::MintTo {
+                        account,
+                        amount,
+                        supply,
+                    },
+                    rest,
+                )
+            }
+            3 => {
+                let (account, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                let (unlock_at, rest) = unpack_u64(rest)?;
+                (
+                    Self::Freeze {
+                        account,
+                        amount,
+                        unlock_at: unlock_at as i64,
+                    },
+                    rest,
+                )
+            }
+            4 => {
+                let (borrower, rest) = unpack_pubkey(rest)?;
+                let (lender, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                (
+                    Self::Borrow {
+                        borrower,
+                        lender,
+                        amount,
+                    },
+                    rest,
+                )
+            }
+            5 => {
+                let (mint, rest) = unpack_pubkey(rest)?;
+                let (authority, rest) = unpack_pubkey(rest)?;
+                (Self::InitMint { mint, authority }, rest)
+            }
+            6 => {
+                let (account, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                let (supply, rest) = unpack_u64(rest)?;
+                (
+                    Self::Burn {
+                        account,
+                        amount,
+                        supply,
+                    },
+                    rest,
+                )
+            }
+            7 => {
+                let (account, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::Thaw { account, amount }, rest)
+            }
+            8 => {
+                let (debtor, rest) = unpack_pubkey(rest)?;
+                let (creditor, rest) = unpack_pubkey(rest)?;
+                let (principal, rest) = unpack_u64(rest)?;
+                let (interest, rest) = unpack_u64(rest)?;
+                (
+                    Self::Repay {
+                        debtor,
+                        creditor,
+                        principal,
+                        interest,
+                    },
+                    rest,
+                )
+            }
+            9 => {
+                let (lender, rest) = unpack_pubkey(rest)?;
+                let (borrower, rest) = unpack_pubkey(rest)?;
+                let (rate_bps, rest) = unpack_u16(rest)?;
+                (
+                    Self::SetRate {
+                        lender,
+                        borrower,
+                        rate_bps,
+                    },
+                    rest,
+                )
+            }
+            10 => {
+                let (lender, rest) = unpack_pubkey(rest)?;
+                let (borrower, rest) = unpack_pubkey(rest)?;
+                let (limit, rest) = unpack_u64(rest)?;
+                (
+                    Self::SetCreditLimit {
+                        lender,
+                        borrower,
+                        limit,
+                    },
+                    rest,
+                )
+            }
+            11 => {
+                let (lender, rest) = unpack_pubkey(rest)?;
+                let (borrower, rest) = unpack_pubkey(rest)?;
+                let (ratio_bps, rest) = unpack_u16(rest)?;
+                let (due_at, rest) = unpack_u64(rest)?;
+                (
+                    Self::SetTerms {
+                        lender,
+                        borrower,
+                        ratio_bps,
+                        due_at: due_at as i64,
+                    },
+                    rest,
+                )
+            }
+            12..=14 => {
+                let (borrower, rest) = unpack_pubkey(rest)?;
+                let (lender, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                let event = match tag {
+                    12 => Self::LockCollateral {
+                        borrower,
+                        lender,
+                        amount,
+                    },
+                    13 => Self::ReleaseCollateral {
+                        borrower,
+                        lender,
+                        amount,
+                    },
+                    _ => Self::Liquidate {
+                        borrower,
+                        lender,
+                        seized: amount,
+                    },
+                };
+                (event, rest)
+            }
+            15 => {
+                let (account, rest) = unpack_pubkey(rest)?;
+                let (delegate, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                (
+                    Self::Approve {
+                        account,
+                        delegate,
+                        amount,
+                    },
+                    rest,
+                )
+            }
+            16 => {
+                let (account, rest) = unpack_pubkey(rest)?;
+                (Self::Revoke { account }, rest)
+            }
+            18 => {
+                let (multisig, rest) = unpack_pubkey(rest)?;
+                let (m, rest) = unpack_u8(rest)?;
+                let (n, rest) = unpack_u8(rest)?;
+                (Self::InitMultisig { multisig, m, n }, rest)
+            }
+            19 => {
+                let (account, rest) = unpack_pubkey(rest)?;
+                let (destination, rest) = unpack_pubkey(rest)?;
+                (
+                    Self::CloseAccount {
+                        account,
+                        destination,
+                    },
+                    rest,
+                )
+            }
+            21 => {
+                let (escrow, rest) = unpack_pubkey(rest)?;
+                let (recipient, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                let (expiry_slot, rest) = unpack_u64(rest)?;
+                (
+                    Self::LockHtlc {
+                        escrow,
+                        recipient,
+                        amount,
+                        expiry_slot,
+                    },
+                    rest,
+                )
+            }
+            22 => {
+                let (escrow, rest) = unpack_pubkey(rest)?;
+                let (recipient, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                (
+                    Self::ClaimHtlc {
+                        escrow,
+                        recipient,
+                        amount,
+                    },
+                    rest,
+                )
+            }
+            23 => {
+                let (escrow, rest) = unpack_pubkey(rest)?;
+                let (sender, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                (
+                    Self::ReclaimHtlc {
+                        escrow,
+                        sender,
+                        amount,
+                    },
+                    rest,
+                )
+            }
+            24 => {
+                let (vesting, rest) = unpack_pubkey(rest)?;
+                let (beneficiary, rest) = unpack_pubkey(rest)?;
+                let (total, rest) = unpack_u64(rest)?;
+                (
+                    Self::CreateVesting {
+                        vesting,
+                        beneficiary,
+                        total,
+                    },
+                    rest,
+                )
+            }
+            25 => {
+                let (vesting, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::Release { vesting, amount }, rest)
+            }
+            26 => {
+                let (vesting, rest) = unpack_pubkey(rest)?;
+                let (vested, rest) = unpack_u64(rest)?;
+                let (returned, rest) = unpack_u64(rest)?;
+                (
+                    Self::RevokeVesting {
+                        vesting,
+                        vested,
+                        returned,
+                    },
+                    rest,
+                )
+            }
+            27 => {
+                let (stream, rest) = unpack_pubkey(rest)?;
+                let (recipient, rest) = unpack_pubkey(rest)?;
+                let (deposit, rest) = unpack_u64(rest)?;
+                let (rate, rest) = unpack_u64(rest)?;
+                (
+                    Self::CreateStream {
+                        stream,
+                        recipient,
+                        deposit,
+                        rate,
+                    },
+                    rest,
+                )
+            }
+            28 => {
+                let (stream, rest) = unpack_pubkey(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::Withdraw { stream, amount }, rest)
+            }
+            29 => {
+                let (stream, rest) = unpack_pubkey(rest)?;
+                let (paid, rest) = unpack_u64(rest)?;
+                let (refunded, rest) = unpack_u64(rest)?;
+                (
+                    Self::CancelStream {
+                        stream,
+                        paid,
+                        refunded,
+                    },
+                    rest,
+                )
+            }
+            30 => {
+                let (fee_bps, rest) = unpack_u16(rest)?;
+                let (max_fee, rest) = unpack_u64(rest)?;
+                let (treasury, rest) = unpack_pubkey(rest)?;
+                (
+                    Self::SetTransferFee {
+                        fee_bps,
+                        max_fee,
+                        treasury,
+                    },
+                    rest,
+                )
+            }
+            31 => {
+                let (config, rest) = unpack_pubkey(rest)?;
+                let (admin, rest) = unpack_pubkey(rest)?;
+                (Self::InitConfig { config, admin }, rest)
+            }
+            32 => {
+                let (pauser, rest) = unpack_pubkey(rest)?;
+                (Self::Pause { pauser }, rest)
+            }
+            33 => {
+                let (pauser, rest) = unpack_pubkey(rest)?;
+                (Self::Unpause { pauser }, rest)
+            }
+            34 => {
+                let (role, rest) = unpack_role(rest)?;
+                let (holder, rest) = unpack_pubkey(rest)?;
+                (Self::GrantRole { role, holder }, rest)
+            }
+            35 => {
+                let (role, rest) = unpack_role(rest)?;
+                (Self::RevokeRole { role }, rest)
+            }
+            36 => {
+                let (account, rest) = unpack_pubkey(rest)?;
+                let (reason, rest) = unpack_u16(rest)?;
+                (Self::FreezeAccount { account, reason }, rest)
+            }
+            37 => {
+                let (account, rest) = unpack_pubkey(rest)?;
+                (Self::ThawAccount { account }, rest)
+            }
+            38 => {
+                let (metadata, rest) = unpack_pubkey(rest)?;
+                let (mint, rest) = unpack_pubkey(rest)?;
+                let (decimals, rest) = unpack_u8(rest)?;
+                (
+                    Self::InitMetadata {
+                        metadata,
+                        mint,
+                        decimals,
+                    },
+                    rest,
+                )
+            }
+            39 => {
+                let (metadata, rest) = unpack_pubkey(rest)?;
+                let (update_authority, rest) = unpack_pubkey(rest)?;
+                (
+                    Self::UpdateMetadata {
+                        metadata,
+                        update_authority,
+                    },
+                    rest,
+                )
+            }
+            40 => {
+                let (legacy, rest) = unpack_pubkey(rest)?;
+                let (account, rest) = unpack_pubkey(rest)?;
+                let (owner, rest) = unpack_pubkey(rest)?;
+                (
+                    Self::Migrate {
+                        legacy,
+                        account,
+                        owner,
+                    },
+                    rest,
+                )
+            }
+            41 => {
+                let (mint, rest) = unpack_pubkey(rest)?;
+                let (enabled, rest) = match unpack_u8(rest)? {
+                    (0, rest) => (false, rest),
+                    (1, rest) => (true, rest),
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                };
+                (Self::SetConfigRoles { mint, enabled }, rest)
+            }
+            64 => {
+                let (log, rest) = unpack_pubkey(rest)?;
+                (Self::InitLog { log }, rest)
+            }
+            65 => {
+                let (book, rest) = unpack_pubkey(rest)?;
+                (Self::InitBook { book }, rest)
+            }
+            66 => {
+                let (log, rest) = unpack_pubkey(rest)?;
+                let (direction, rest) = unpack_u8(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                let (interest, rest) = unpack_u64(rest)?;
+                (
+                    Self::LogUpdated {
+                        log,
+                        direction,
+                        amount,
+                        interest,
+                    },
+                    rest,
+                )
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        expect_end(rest)?;
+        Ok(event)
+    }
+
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = vec![EVENT_VERSION];
+        match self {
+            Self::InitAccount { account, owner } => {
+                data.push(0);
+                data.extend_from_slice(account.as_ref());
+                data.extend_from_slice(owner.as_ref());
+            }
+            Self::Transfer {
+                from,
+                to,
+                amount,
+                fee,
+            } => {
+                data.push(1);
+                data.extend_from_slice(from.as_ref());
+                data.extend_from_slice(to.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+                data.extend_from_slice(&fee.to_be_bytes());
+            }
+            Self::MintTo {
+                account,
+                amount,
+                supply,
+            } => {
+                data.push(2);
+                data.extend_from_slice(account.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+                data.extend_from_slice(&supply.to_be_bytes());
+            }
+            Self::Freeze {
+                account,
+                amount,
+                unlock_at,
+            } => {
+                data.push(3);
+                data.extend_from_slice(account.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+                data.extend_from_slice(&unlock_at.to_be_bytes());
+            }
+            Self::Borrow {
+                borrower,
+                lender,
+                amount,
+            } => {
+                data.push(4);
+                data.extend_from_slice(borrower.as_ref());
+                data.extend_from_slice(lender.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::InitMint { mint, authority } => {
+                data.push(5);
+                data.extend_from_slice(mint.as_ref());
+                data.extend_from_slice(authority.as_ref());
+            }
+            Self::Burn {
+                account,
+                amount,
+                supply,
+            } => {
+                data.push(6);
+                data.extend_from_slice(account.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+                data.extend_from_slice(&supply.to_be_bytes());
+            }
+            Self::Thaw { account, amount } => {
+                data.push(7);
+                data.extend_from_slice(account.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::Repay {
+                debtor,
+                creditor,
+                principal,
+                interest,
+            } => {
+                data.push(8);
+                data.extend_from_slice(debtor.as_ref());
+                data.extend_from_slice(creditor.as_ref());
+                data.extend_from_slice(&principal.to_be_bytes());
+                data.extend_from_slice(&interest.to_be_bytes());
+            }
+            Self::SetRate {
+                lender,
+                borrower,
+                rate_bps,
+            } => {
+                data.push(9);
+                data.extend_from_slice(lender.as_ref());
+                data.extend_from_slice(borrower.as_ref());
+                data.extend_from_slice(&rate_bps.to_be_bytes());
+            }
+            Self::SetCreditLimit {
+                lender,
+                borrower,
+                limit,
+            } => {
+                data.push(10);
+                data.extend_from_slice(lender.as_ref());
+                data.extend_from_slice(borrower.as_ref());
+                data.extend_from_slice(&limit.to_be_bytes());
+            }
+            Self::SetTerms {
+                lender,
+                borrower,
+                ratio_bps,
+                due_at,
+            } => {
+                data.push(11);
+                data.extend_from_slice(lender.as_ref());
+                data.extend_from_slice(borrower.as_ref());
+                data.extend_from_slice(&ratio_bps.to_be_bytes());
+                data.extend_from_slice(&due_at.to_be_bytes());
+            }
+            Self::LockCollateral {
+                borrower,
+                lender,
+                amount,
+            }
+            | Self::ReleaseCollateral {
+                borrower,
+                lender,
+                amount,
+            }
+            | Self::Liquidate {
+                borrower,
+                lender,
+                seized: amount,
+            } => {
+                data.push(match self {
+                    Self::LockCollateral { .. } => 12,
+                    Self::ReleaseCollateral { .. } => 13,
+                    _ => 14,
+                });
+                data.extend_from_slice(borrower.as_ref());
+                data.extend_from_slice(lender.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::Approve {
+                account,
+                delegate,
+                amount,
+            } => {
+                data.push(15);
+                data.extend_from_slice(account.as_ref());
+                data.extend_from_slice(delegate.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::Revoke { account } => {
+                data.push(16);
+                data.extend_from_slice(account.as_ref());
+            }
+            Self::InitMultisig { multisig, m, n } => {
+                data.push(18);
+                data.extend_from_slice(multisig.as_ref());
+                data.push(*m);
+                data.push(*n);
+            }
+            Self::CloseAccount {
+                account,
+                destination,
+            } => {
+                data.push(19);
+                data.extend_from_slice(account.as_ref());
+                data.extend_from_slice(destination.as_ref());
+            }
+            Self::LockHtlc {
+                escrow,
+                recipient,
+                amount,
+                expiry_slot,
+            } => {
+                data.push(21);
+                data.extend_from_slice(escrow.as_ref());
+                data.extend_from_slice(recipient.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+                data.extend_from_slice(&expiry_slot.to_be_bytes());
+            }
+            Self::ClaimHtlc {
+                escrow,
+                recipient,
+                amount,
+            } => {
+                data.push(22);
+                data.extend_from_slice(escrow.as_ref());
+                data.extend_from_slice(recipient.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::ReclaimHtlc {
+                escrow,
+                sender,
+                amount,
+            } => {
+                data.push(23);
+                data.extend_from_slice(escrow.as_ref());
+                data.extend_from_slice(sender.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::CreateVesting {
+                vesting,
+                beneficiary,
+                total,
+            } => {
+                data.push(24);
+                data.extend_from_slice(vesting.as_ref());
+                data.extend_from_slice(beneficiary.as_ref());
+                data.extend_from_slice(&total.to_be_bytes());
+            }
+            Self::Release { vesting, amount } => {
+                data.push(25);
+                data.extend_from_slice(vesting.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::RevokeVesting {
+                vesting,
+                vested,
+                returned,
+            } => {
+                data.push(26);
+                data.extend_from_slice(vesting.as_ref());
+                data.extend_from_slice(&vested.to_be_bytes());
+                data.extend_from_slice(&returned.to_be_bytes());
+            }
+            Self::CreateStream {
+                stream,
+                recipient,
+                deposit,
+                rate,
+            } => {
+                data.push(27);
+                data.extend_from_slice(stream.as_ref());
+                data.extend_from_slice(recipient.as_ref());
+                data.extend_from_slice(&deposit.to_be_bytes());
+                data.extend_from_slice(&rate.to_be_bytes());
+            }
+            Self::Withdraw { stream, amount } => {
+                data.push(28);
+                data.extend_from_slice(stream.as_ref());
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::CancelStream {
+                stream,
+                paid,
+                refunded,
+            } => {
+                data.push(29);
+                data.extend_from_slice(stream.as_ref());
+                data.extend_from_slice(&paid.to_be_bytes());
+                data.extend_from_slice(&refunded.to_be_bytes());
+            }
+            Self::SetTransferFee {
+                fee_bps,
+                max_fee,
+                treasury,
+            } => {
+                data.push(30);
+                data.extend_from_slice(&fee_bps.to_be_bytes());
+                data.extend_from_slice(&max_fee.to_be_bytes());
+                data.extend_from_slice(treasury.as_ref());
+            }
+            Self::InitConfig { config, admin } => {
+                data.push(31);
+                data.extend_from_slice(config.as_ref());
+                data.extend_from_slice(admin.as_ref());
+            }
+            Self::Pause { pauser } => {
+                data.push(32);
+                data.extend_from_slice(pauser.as_ref());
+            }
+            Self::Unpause { pauser } => {
+                data.push(33);
+                data.extend_from_slice(pauser.as_ref());
+            }
+            Self::GrantRole { role, holder } => {
+                data.push(34);
+                data.push(*role as u8);
+                data.extend_from_slice(holder.as_ref());
+            }
+            Self::RevokeRole { role } => {
+                data.push(35);
+                data.push(*role as u8);
+            }
+            Self::FreezeAccount { account, reason } => {
+                data.push(36);
+                data.extend_from_slice(account.as_ref());
+                data.extend_from_slice(&reason.to_be_bytes());
+            }
+            Self::ThawAccount { account } => {
+                data.push(37);
+                data.extend_from_slice(account.as_ref());
+            }
+            Self::InitMetadata {
+                metadata,
+                mint,
+                decimals,
+            } => {
+                data.push(38);
+                data.extend_from_slice(metadata.as_ref());
+                data.extend_from_slice(mint.as_ref());
+                data.push(*decimals);
+            }
+            Self::UpdateMetadata {
+                metadata,
+                update_authority,
+            } => {
+                data.push(39);
+                data.extend_from_slice(metadata.as_ref());
+                data.extend_from_slice(update_authority.as_ref());
+            }
+            Self::Migrate {
+                legacy,
+                account,
+                owner,
+            } => {
+                data.push(40);
+                data.extend_from_slice(legacy.as_ref());
+                data.extend_from_slice(account.as_ref());
+                data.extend_from_slice(owner.as_ref());
+            }
+            Self::SetConfigRoles { mint, enabled } => {
+                data.push(41);
+                data.extend_from_slice(mint.as_ref());
+                data.push(*enabled as u8);
+            }
+            Self::InitLog { log } => {
+                data.push(64);
+                data.extend_from_slice(log.as_ref());
+            }
+            Self::InitBook { book } => {
+                data.push(65);
+                data.extend_from_slice(book.as_ref());
+            }
+            Self::LogUpdated {
+                log,
+                direction,
+                amount,
+                interest,
+            } => {
+                data.push(66);
+                data.extend_from_slice(log.as_ref());
+                data.push(*direction);
+                data.extend_from_slice(&amount.to_be_bytes());
+                data.extend_from_slice(&interest.to_be_bytes());
+            }
+        }
+        data
+    }
+
+    /// Write the event to the program log as one `sol_log_data` field.
+    pub fn emit(&self) {
+        solana_program::log::sol_log_data(&[&self.pack()]);
+    }
+}
+
+fn unpack_pubkey(input: &[u8]) -> Result<(Pubkey, &[u8]), ProgramError> {
+    let (raw, rest) = unpack_bytes32(input)?;
+    Ok((Pubkey::new_from_array(raw), rest))
+}
+
+fn unpack_role(input: &[u8]) -> Result<(Role, &[u8]), ProgramError> {
+    let (role, rest) = unpack_u8(input)?;
+    let role = Role::from_u8(role).ok_or(ProgramError::InvalidInstructionData)?;
+    Ok((role, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::events;
+
+    #[test]
+    fn round_trip() {
+        for event in events() {
+            let data = event.pack();
+            assert_eq!(data[0], EVENT_VERSION);
+            assert_eq!(Event::unpack(&data), Ok(event));
+        }
+    }
+
+    #[test]
+    fn layout_is_big_endian() {
+        let data = Event::Transfer {
+            from: Pubkey::new_from_array([1; 32]),
+            to: Pubkey::new_from_array([2; 32]),
+            amount: 0x0102,
+            fee: 3,
+        }
+        .pack();
+        assert_eq!(data.len(), 2 + 32 + 32 + 8 + 8);
+        assert_eq!(&data[..2], &[EVENT_VERSION, 1]);
+        assert_eq!(&data[66..74], &[0, 0, 0, 0, 0, 0, 1, 2]);
+        assert_eq!(&data[74..], &[0, 0, 0, 0, 0, 0, 0, 3]);
+    }
+
+    #[test]
+    fn truncated_or_trailing_input() {
+        for event in events() {
+            let data = event.pack();
+            for len in 0..data.len() {
+                assert_eq!(
+                    Event::unpack(&data[..len]),
+                    Err(ProgramError::InvalidInstructionData),
+                    "{:?} cut to {} bytes",
+                    event,
+                    len
+                );
+            }
+            let mut long = data;
+            long.push(0);
+            assert_eq!(
+                Event::unpack(&long),
+                Err(ProgramError::InvalidInstructionData)
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_version_tag_and_values() {
+        let mut data = Event::Pause {
+            pauser: Pubkey::new_unique(),
+        }
+        .pack();
+        data[0] = EVENT_VERSION + 1;
+        assert_eq!(
+            Event::unpack(&data),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        for tag in [42, 63, 67, u8::MAX] {
+            assert_eq!(
+                Event::unpack(&[EVENT_VERSION, tag]),
+                Err(ProgramError::InvalidInstructionData)
+            );
+        }
+        let mut role = Event::RevokeRole { role: Role::Minter }.pack();
+        role[2] = Role::ALL.len() as u8;
+        assert_eq!(
+            Event::unpack(&role),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        let mut enabled = Event::SetConfigRoles {
+            mint: Pubkey::new_unique(),
+            enabled: false,
+        }
+        .pack();
+        enabled[34] = 2;
+        assert_eq!(
+            Event::unpack(&enabled),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
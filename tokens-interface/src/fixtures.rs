@@ -0,0 +1,253 @@
+//! Values shared by the tests of the state, event and instruction layouts.
+use solana_program::pubkey::Pubkey;
+
+use crate::event::Event;
+use crate::instruction::TokenInstruction;
+use crate::log_instruction::LogInstruction;
+use crate::log_state::CreditLog;
+use crate::state::{Metadata, Mint, Role, Stream, TokenAccount, Vesting};
+
+pub fn token_account() -> TokenAccount {
+    TokenAccount {
+        owner: Pubkey::new_from_array([1; 32]),
+        balance: 100,
+        frozen: 20,
+        borrowed: 3,
+        debit: 4,
+        unlock_at: -5,
+        collateral: 6,
+        delegate: Some(Pubkey::new_from_array([2; 32])),
+        allowance: 7,
+        freeze_reason: Some(8),
+        mint: Pubkey::new_from_array([3; 32]),
+    }
+}
+
+pub fn vesting() -> Vesting {
+    Vesting {
+        total: 1_000,
+        start: 100,
+        cliff: 200,
+        end: 500,
+        ..Default::default()
+    }
+}
+
+pub fn stream() -> Stream {
+    Stream {
+        deposit: 1_000,
+        rate: 10,
+        start: 100,
+        ..Default::default()
+    }
+}
+
+pub fn mint(fee_bps: u16, max_fee: u64) -> Mint {
+    Mint {
+        fee_bps,
+        max_fee,
+        ..Default::default()
+    }
+}
+
+pub fn metadata(decimals: u8, symbol: &str) -> Metadata {
+    Metadata {
+        decimals,
+        symbol: symbol.to_string(),
+        ..Default::default()
+    }
+}
+
+pub fn credit_log(amount: u64, rate_bps: u16) -> CreditLog {
+    CreditLog {
+        amount,
+        rates: [rate_bps, 0],
+        last_accrual: 1_000,
+        ..Default::default()
+    }
+}
+
+pub fn events() -> Vec<Event> {
+    let a = Pubkey::new_from_array([1; 32]);
+    let b = Pubkey::new_from_array([2; 32]);
+    vec![
+        Event::InitAccount {
+            account: a,
+            owner: b,
+        },
+        Event::Transfer {
+            from: a,
+            to: b,
+            amount: u64::MAX,
+            fee: 7,
+        },
+        Event::Freeze {
+            account: a,
+            amount: 5,
+            unlock_at: -1,
+        },
+        Event::SetTerms {
+            lender: a,
+            borrower: b,
+            ratio_bps: 15_000,
+            due_at: 1_700_000_000,
+        },
+        Event::InitMultisig {
+            multisig: a,
+            m: 2,
+            n: 3,
+        },
+        Event::GrantRole {
+            role: Role::FeeManager,
+            holder: b,
+        },
+        Event::RevokeRole { role: Role::Pauser },
+        Event::Migrate {
+            legacy: a,
+            account: b,
+            owner: a,
+        },
+        Event::SetConfigRoles {
+            mint: a,
+            enabled: true,
+        },
+        Event::InitLog { log: a },
+        Event::LogUpdated {
+            log: b,
+            direction: 1,
+            amount: 100,
+            interest: 3,
+        },
+    ]
+}
+
+pub fn token_instructions() -> Vec<TokenInstruction> {
+    vec![
+        TokenInstruction::InitAccount { bump: 254 },
+        TokenInstruction::Transfer { amount: 1 },
+        TokenInstruction::MintTo { amount: u64::MAX },
+        TokenInstruction::Freeze {
+            amount: 5,
+            unlock_at: None,
+        },
+        TokenInstruction::Freeze {
+            amount: 5,
+            unlock_at: Some(-7),
+        },
+        TokenInstruction::Borrow { amount: 10 },
+        TokenInstruction::InitMint { supply_cap: None },
+        TokenInstruction::InitMint {
+            supply_cap: Some(1_000),
+        },
+        TokenInstruction::Burn { amount: 3 },
+        TokenInstruction::Thaw { amount: 4 },
+        TokenInstruction::Repay { amount: 6 },
+        TokenInstruction::SetRate { rate_bps: 500 },
+        TokenInstruction::SetCreditLimit { limit: 9 },
+        TokenInstruction::SetTerms {
+            ratio_bps: 15_000,
+            due_at: Some(1_700_000_000),
+        },
+        TokenInstruction::LockCollateral { amount: 11 },
+        TokenInstruction::ReleaseCollateral { amount: 12 },
+        TokenInstruction::Liquidate,
+        TokenInstruction::Approve { amount: 13 },
+        TokenInstruction::Revoke,
+        TokenInstruction::TransferFrom { amount: 14 },
+        TokenInstruction::InitMultisig { m: 2, bump: 207 },
+        TokenInstruction::CloseAccount { logs: 2 },
+        TokenInstruction::TransferBatch {
+            amounts: vec![1, 2, 3],
+        },
+        TokenInstruction::LockHtlc {
+            bump: 200,
+            amount: 15,
+            hash: [7; 32],
+            expiry_slot: 99,
+        },
+        TokenInstruction::ClaimHtlc { preimage: [8; 32] },
+        TokenInstruction::ReclaimHtlc,
+        TokenInstruction::CreateVesting {
+            bump: 201,
+            total: 1_000,
+            start: 10,
+            cliff: 20,
+            end: 30,
+            revocable: true,
+        },
+        TokenInstruction::Release,
+        TokenInstruction::RevokeVesting,
+        TokenInstruction::CreateStream {
+            bump: 202,
+            deposit: 500,
+            rate: 5,
+        },
+        TokenInstruction::Withdraw,
+        TokenInstruction::CancelStream,
+        TokenInstruction::SetTransferFee {
+            fee_bps: 25,
+            max_fee: 100,
+        },
+        TokenInstruction::InitConfig { bump: 203 },
+        TokenInstruction::Pause,
+        TokenInstruction::Unpause,
+        TokenInstruction::GrantRole {
+            role: Role::FeeManager,
+        },
+        TokenInstruction::RevokeRole { role: Role::Minter },
+        TokenInstruction::FreezeAccount { reason: 3 },
+        TokenInstruction::ThawAccount,
+        TokenInstruction::InitMetadata {
+            bump: 204,
+            decimals: 6,
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            uri: String::new(),
+        },
+        TokenInstruction::UpdateMetadata {
+            name: "Renamed".to_string(),
+            symbol: "RN".to_string(),
+            uri: "https://example.com".to_string(),
+        },
+        TokenInstruction::Migrate {
+            legacy_bump: 205,
+            bump: 206,
+        },
+        TokenInstruction::SetConfigRoles { enabled: true },
+    ]
+}
+
+pub fn log_instructions() -> Vec<LogInstruction> {
+    vec![
+        LogInstruction::InitLog { bump: 254 },
+        LogInstruction::Record {
+            direction: 1,
+            amount: 100,
+            interest: 3,
+            bump: 253,
+        },
+        LogInstruction::SetRate {
+            direction: 0,
+            rate_bps: 500,
+            bump: 252,
+        },
+        LogInstruction::InitBook,
+        LogInstruction::Accrue,
+        LogInstruction::SetLimit {
+            direction: 1,
+            limit: u64::MAX,
+            bump: 251,
+        },
+        LogInstruction::SetTerms {
+            direction: 0,
+            ratio_bps: 15_000,
+            due_at: -1,
+            bump: 250,
+        },
+        LogInstruction::SetCollateral {
+            direction: 1,
+            collateral: 42,
+            bump: 249,
+        },
+    ]
+}
@@ -0,0 +1,1892 @@
+use solana_program::{
+    bpf_loader_upgradeable,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::SysvarId,
+};
+
+use crate::state::Role;
+use crate::{
+    expect_end, log_instruction, pack_option_i64, pack_option_u64, pack_string, unpack_bytes32,
+    unpack_option_i64, unpack_option_u64, unpack_string, unpack_u16, unpack_u64, unpack_u8,
+};
+
+/// Instructions understood by the tokens program.
+/// Every amount is encoded as a big endian u64 after the tag byte.
+/// Except for the config instructions, the config pda follows the listed
+/// accounts and any multisig co-signers as the last account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenInstruction {
+    /// Create the token account pda of the signer for a mint, or of the
+    /// optional owner such as a multisig account.
+    ///
+    /// 0. `[signer, writable]` owner, pays the rent
+    /// 1. `[writable]` token account pda `[owner, mint]`
+    /// 2. `[]` mint
+    /// 3. `[]` rent sysvar
+    /// 4. `[]` system program
+    /// 5. `[]` owner, optional
+    InitAccount { bump: u8 },
+    /// Move balance to another token account, the transfer fee of the mint
+    /// is withheld from the amount into the treasury.
+    ///
+    /// 0. `[signer]` owner
+    /// 1. `[writable]` source token account
+    /// 2. `[writable]` destination token account
+    /// 3. `[]` mint of both token accounts
+    /// 4. `[writable]` treasury token account of the mint
+    Transfer { amount: u64 },
+    /// Mint new tokens to a token account, signed by the mint authority.
+    ///
+    /// 0. `[signer]` mint authority, or the minter of a mint using config roles
+    /// 1. `[writable]` token account
+    /// 2. `[writable]` mint of the token account
+    MintTo { amount: u64 },
+    /// Move balance into the frozen bucket. With `unlock_at` the frozen bucket
+    /// can not be thawed by the owner before that unix timestamp.
+    ///
+    /// 0. `[signer]` owner
+    /// 1. `[writable]` token account
+    Freeze { amount: u64, unlock_at: Option<i64> },
+    /// Borrow from another token account and record it in the log program.
+    /// The amount moves from the lender balance to the borrower, less the
+    /// transfer fee, so both owners sign. The full amount is owed.
+    ///
+    /// 0. `[signer, writable]` borrower, pays the log account rent
+    /// 1. `[writable]` borrower token account
+    /// 2. `[writable]` lender token account
+    /// 3. `[writable]` log pda of the pair
+    /// 4. `[]` rent sysvar
+    /// 5. `[]` system program
+    /// 6. `[]` clock sysvar
+    /// 7. `[]` log program
+    /// 8. `[]` log authority pda `[b"log"]`
+    /// 9. `[signer]` lender
+    /// 10. `[]` mint of the token accounts
+    /// 11. `[writable]` treasury token account of the mint
+    Borrow { amount: u64 },
+    /// Create a mint at a new keypair account, the signer or the optional
+    /// authority account becomes the mint authority. One program serves any
+    /// number of mints, each with its own token accounts.
+    ///
+    /// 0. `[signer, writable]` mint authority, pays the rent
+    /// 1. `[signer, writable]` mint account
+    /// 2. `[]` rent sysvar
+    /// 3. `[]` system program
+    /// 4. `[]` mint authority, optional
+    InitMint { supply_cap: Option<u64> },
+    /// Destroy balance of a token account and lower the supply.
+    ///
+    /// 0. `[signer]` owner
+    /// 1. `[writable]` token account
+    /// 2. `[writable]` mint of the token account
+    Burn { amount: u64 },
+    /// Move frozen balance back to the balance. The owner can thaw once the
+    /// unlock timestamp has passed, the mint authority can thaw at any time.
+    ///
+    /// 0. `[signer]` owner or mint authority
+    /// 1. `[writable]` token account
+    /// 2. `[]` clock sysvar
+    /// 3. `[]` mint of the token account
+    Thaw { amount: u64 },
+    /// Pay back part of a borrow, refusing more than is owed to the lender.
+    /// The transfer fee is withheld from the amount, which counts in full
+    /// against the debt.
+    ///
+    /// Accounts 0 to 8 are the same as `Borrow`, with the debtor as signer
+    /// and the creditor as the lender.
+    ///
+    /// 9. `[]` mint of the token accounts
+    /// 10. `[writable]` treasury token account of the mint
+    Repay { amount: u64 },
+    /// Set the annual rate in basis points the signer charges the counterparty.
+    ///
+    /// Accounts 0 to 8 are the same as `Borrow`, with the lender as signer
+    /// and the borrower as the counterparty.
+    SetRate { rate_bps: u16 },
+    /// Set how much the signer lends the counterparty at most, borrow fails
+    /// once the owed principal would exceed it.
+    ///
+    /// Accounts are the same as `SetRate`.
+    SetCreditLimit { limit: u64 },
+    /// Set the collateral the signer asks for in basis points of the debt and
+    /// when the debt is due. While the counterparty owes the signer the terms
+    /// can only be loosened.
+    ///
+    /// Accounts are the same as `SetRate`.
+    SetTerms { ratio_bps: u16, due_at: Option<i64> },
+    /// Lock frozen balance of the signer as collateral for the credit line of
+    /// the lender.
+    ///
+    /// Accounts 0 to 8 are the same as `Borrow`.
+    LockCollateral { amount: u64 },
+    /// Unlock collateral the remaining debt does not need.
+    ///
+    /// Accounts 0 to 8 are the same as `Borrow`.
+    ReleaseCollateral { amount: u64 },
+    /// Seize the collateral of a borrower to the lender once the debt is past
+    /// due or under-collateralized, anyone can call it. The lender receives
+    /// the seized amount less the transfer fee.
+    ///
+    /// 0. `[signer, writable]` caller, pays the log account rent
+    /// 1. `[writable]` borrower token account
+    /// 2. `[writable]` lender token account
+    /// 3. `[writable]` log pda of the pair
+    /// 4. `[]` rent sysvar
+    /// 5. `[]` system program
+    /// 6. `[]` clock sysvar
+    /// 7. `[]` log program
+    /// 8. `[]` log authority pda `[b"log"]`
+    /// 9. `[]` mint of the token accounts
+    /// 10. `[writable]` treasury token account of the mint
+    Liquidate,
+    /// Let a delegate move up to `amount` of the balance, replacing any
+    /// previous delegate.
+    ///
+    /// 0. `[signer]` owner
+    /// 1. `[writable]` token account
+    /// 2. `[]` delegate
+    Approve { amount: u64 },
+    /// Remove the delegate of a token account.
+    ///
+    /// 0. `[signer]` owner
+    /// 1. `[writable]` token account
+    Revoke,
+    /// Move balance as the delegate, lowering the allowance.
+    ///
+    /// 0. `[signer]` delegate
+    /// 1. `[writable]` source token account
+    /// 2. `[writable]` destination token account
+    /// 3. `[]` mint of both token accounts
+    /// 4. `[writable]` treasury token account of the mint
+    TransferFrom { amount: u64 },
+    /// Create an M-of-N multisig account from the distinct signer keys. The
+    /// account is a pda, so only its keys can act for it.
+    ///
+    /// 0. `[signer, writable]` payer
+    /// 1. `[writable]` multisig pda `[b"multisig", m, signer keys]`
+    /// 2. `[]` rent sysvar
+    /// 3. `[]` system program
+    /// 4. ..4+N `[]` signer keys
+    InitMultisig { m: u8, bump: u8 },
+    /// Close an empty token account, its lamports go to the destination. The
+    /// credit logs of the account with each counterparty it dealt with must
+    /// hold no principal and no unpaid interest.
+    ///
+    /// 0. `[signer]` owner
+    /// 1. `[writable]` token account
+    /// 2. `[writable]` destination
+    /// 3. ..3+logs `[]` credit log pdas of the token account
+    CloseAccount { logs: u8 },
+    /// Move balance to many token accounts at once, the amounts are encoded
+    /// as a count byte followed by one u64 per destination.
+    ///
+    /// 0. `[signer]` owner
+    /// 1. `[writable]` source token account
+    /// 2. ..2+N `[writable]` destination token accounts
+    /// 3. `[]` mint of every token account, after the N destinations
+    /// 4. `[writable]` treasury token account of the mint
+    TransferBatch { amounts: Vec<u64> },
+    /// Move balance into an escrow the recipient can claim with the preimage
+    /// of the sha-256 `hash` before `expiry_slot`.
+    ///
+    /// 0. `[signer, writable]` sender, pays the rent
+    /// 1. `[writable]` sender token account
+    /// 2. `[]` recipient token account
+    /// 3. `[writable]` escrow pda `[b"htlc", sender, mint, recipient, hash]`
+    /// 4. `[]` rent sysvar
+    /// 5. `[]` system program
+    /// 6. `[]` clock sysvar
+    LockHtlc {
+        bump: u8,
+        amount: u64,
+        hash: [u8; 32],
+        expiry_slot: u64,
+    },
+    /// Pay the escrow less the transfer fee to the recipient by revealing the
+    /// preimage, anyone can submit it. The rent goes back to the sender.
+    ///
+    /// 0. `[signer]` caller
+    /// 1. `[writable]` escrow pda
+    /// 2. `[writable]` recipient token account
+    /// 3. `[writable]` sender
+    /// 4. `[]` clock sysvar
+    /// 5. `[]` mint of the token accounts
+    /// 6. `[writable]` treasury token account of the mint
+    ClaimHtlc { preimage: [u8; 32] },
+    /// Return an expired escrow to a token account of the sender, without a
+    /// fee since the balance goes back to its owner.
+    ///
+    /// 0. `[signer, writable]` sender
+    /// 1. `[writable]` escrow pda
+    /// 2. `[writable]` sender token account
+    /// 3. `[]` clock sysvar
+    /// 4. `[]` recipient token account, for its mint
+    ReclaimHtlc,
+    /// Move balance of the grantor into a vesting grant for the beneficiary,
+    /// `start <= cliff <= end` are unix timestamps.
+    ///
+    /// 0. `[signer, writable]` grantor, pays the rent
+    /// 1. `[writable]` grantor token account
+    /// 2. `[]` beneficiary token account
+    /// 3. `[writable]` vesting pda `[b"vesting", grantor, beneficiary token account]`
+    /// 4. `[]` rent sysvar
+    /// 5. `[]` system program
+    CreateVesting {
+        bump: u8,
+        total: u64,
+        start: i64,
+        cliff: i64,
+        end: i64,
+        revocable: bool,
+    },
+    /// Credit the vested and not yet released part less the transfer fee to
+    /// the beneficiary, anyone can call it. The rent goes back to the grantor
+    /// once all is released.
+    ///
+    /// 0. `[signer]` caller
+    /// 1. `[writable]` vesting pda
+    /// 2. `[writable]` beneficiary token account
+    /// 3. `[writable]` grantor
+    /// 4. `[]` clock sysvar
+    /// 5. `[]` mint of the token accounts
+    /// 6. `[writable]` treasury token account of the mint
+    Release,
+    /// Release the vested part less the transfer fee and return the rest to a
+    /// token account of the grantor, which is not charged a fee.
+    ///
+    /// 0. `[signer, writable]` grantor
+    /// 1. `[writable]` vesting pda
+    /// 2. `[writable]` beneficiary token account
+    /// 3. `[writable]` grantor token account
+    /// 4. `[]` clock sysvar
+    /// 5. `[]` mint of the token accounts
+    /// 6. `[writable]` treasury token account of the mint
+    RevokeVesting,
+    /// Deposit balance of the sender into a stream paying `rate` per second
+    /// to the recipient from now on.
+    ///
+    /// 0. `[signer, writable]` sender, pays the rent
+    /// 1. `[writable]` sender token account
+    /// 2. `[]` recipient token account
+    /// 3. `[writable]` stream pda `[b"stream", sender, recipient token account]`
+    /// 4. `[]` rent sysvar
+    /// 5. `[]` system program
+    /// 6. `[]` clock sysvar
+    CreateStream { bump: u8, deposit: u64, rate: u64 },
+    /// Withdraw everything streamed so far less the transfer fee to the
+    /// recipient token account. The rent goes back to the sender once the
+    /// deposit is withdrawn.
+    ///
+    /// 0. `[signer]` recipient
+    /// 1. `[writable]` stream pda
+    /// 2. `[writable]` recipient token account
+    /// 3. `[writable]` sender
+    /// 4. `[]` clock sysvar
+    /// 5. `[]` mint of the token accounts
+    /// 6. `[writable]` treasury token account of the mint
+    Withdraw,
+    /// Stop a stream, signed by either side. What was streamed goes to the
+    /// recipient less the transfer fee, the rest back to a token account of
+    /// the sender without a fee.
+    ///
+    /// 0. `[signer]` sender or recipient
+    /// 1. `[writable]` stream pda
+    /// 2. `[writable]` recipient token account
+    /// 3. `[writable]` sender token account
+    /// 4. `[writable]` sender
+    /// 5. `[]` clock sysvar
+    /// 6. `[]` mint of the token accounts
+    /// 7. `[writable]` treasury token account of the mint
+    CancelStream,
+    /// Set the fee in basis points withheld from every transfer, at most
+    /// `max_fee` per transfer, and the treasury it goes to.
+    ///
+    /// 0. `[signer]` mint authority, or the fee manager of a mint using config roles
+    /// 1. `[writable]` mint
+    /// 2. `[]` treasury token account of the mint
+    SetTransferFee { fee_bps: u16, max_fee: u64 },
+    /// Create the program config, the signer or the optional admin becomes
+    /// the admin that can pause the program. Only the upgrade authority of
+    /// the program can create it.
+    ///
+    /// 0. `[signer, writable]` upgrade authority, pays the rent
+    /// 1. `[writable]` config pda `[b"config"]`
+    /// 2. `[]` rent sysvar
+    /// 3. `[]` system program
+    /// 4. `[]` program data account of the program
+    /// 5. `[]` admin, optional
+    InitConfig { bump: u8 },
+    /// Stop every instruction but the config ones until `Unpause`.
+    ///
+    /// 0. `[signer]` pauser, or the admin when unassigned
+    /// 1. `[writable]` config pda
+    Pause,
+    /// Resume the program after `Pause`.
+    ///
+    /// Accounts are the same as `Pause`.
+    Unpause,
+    /// Assign `role` to the holder, replacing any previous holder.
+    ///
+    /// 0. `[signer]` admin
+    /// 1. `[writable]` config pda
+    /// 2. `[]` holder
+    GrantRole { role: Role },
+    /// Unassign `role`, handing it back to the mint authority or admin.
+    ///
+    /// 0. `[signer]` admin
+    /// 1. `[writable]` config pda
+    RevokeRole { role: Role },
+    /// Freeze the whole token account for compliance, recording `reason`.
+    /// Until `ThawAccount` the account can neither send nor receive.
+    ///
+    /// 0. `[signer]` mint authority, or the freezer of a mint using config roles
+    /// 1. `[writable]` token account
+    /// 2. `[]` mint of the token account
+    FreezeAccount { reason: u16 },
+    /// Lift the freeze of `FreezeAccount`.
+    ///
+    /// Accounts are the same as `FreezeAccount`.
+    ThawAccount,
+    /// Create the display metadata of a mint, signed by the mint authority
+    /// which becomes the update authority. `decimals` can not change later.
+    ///
+    /// 0. `[signer, writable]` mint authority, pays the rent
+    /// 1. `[writable]` metadata pda `[b"metadata", mint]`
+    /// 2. `[]` mint
+    /// 3. `[]` rent sysvar
+    /// 4. `[]` system program
+    InitMetadata {
+        bump: u8,
+        decimals: u8,
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    /// Replace the name, symbol and uri of a mint and hand the update
+    /// authority to the given account, the signer to keep it.
+    ///
+    /// 0. `[signer]` update authority
+    /// 1. `[writable]` metadata pda
+    /// 2. `[]` update authority from now on
+    UpdateMetadata {
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    /// Move a legacy 80 byte token account into the token account of its owner
+    /// for the mint, which adds its balances to the supply. The token account
+    /// is created by the payer when missing, and the legacy account is closed
+    /// to its owner, who paid its rent. A legacy account with borrowed or lent
+    /// tokens is refused, as its credit logs are keyed on the legacy address,
+    /// and so is a frozen token account.
+    ///
+    /// 0. `[signer]` mint authority, or the upgrader of a mint using config roles
+    /// 1. `[writable]` legacy token account pda `[owner]`
+    /// 2. `[writable]` mint
+    /// 3. `[writable]` owner of the legacy token account
+    /// 4. `[signer, writable]` payer
+    /// 5. `[]` rent sysvar
+    /// 6. `[]` system program
+    /// 7. `[writable]` token account pda `[owner, mint]`
+    Migrate { legacy_bump: u8, bump: u8 },
+    /// Let the role holders of the config act for the mint instead of its
+    /// authority, or hand the mint back to the authority.
+    ///
+    /// 0. `[signer]` mint authority
+    /// 1. `[writable]` mint
+    SetConfigRoles { enabled: bool },
+}
+
+impl TokenInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = unpack_u8(input)?;
+        let (instruction, rest) = match tag {
+            0 => {
+                let (bump, rest) = unpack_u8(rest)?;
+                (Self::InitAccount { bump }, rest)
+            }
+            1 => {
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::Transfer { amount }, rest)
+            }
+            2 => {
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::MintTo { amount }, rest)
+            }
+            3 => {
+                let (amount, rest) = unpack_u64(rest)?;
+                let (unlock_at, rest) = unpack_option_i64(rest)?;
+                (Self::Freeze { amount, unlock_at }, rest)
+            }
+            4 => {
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::Borrow { amount }, rest)
+            }
+            5 => {
+                let (supply_cap, rest) = unpack_option_u64(rest)?;
+                (Self::InitMint { supply_cap }, rest)
+            }
+            6 => {
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::Burn { amount }, rest)
+            }
+            7 => {
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::Thaw { amount }, rest)
+            }
+            8 => {
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::Repay { amount }, rest)
+            }
+            9 => {
+                let (rate_bps, rest) = unpack_u16(rest)?;
+                (Self::SetRate { rate_bps }, rest)
+            }
+            10 => {
+                let (limit, rest) = unpack_u64(rest)?;
+                (Self::SetCreditLimit { limit }, rest)
+            }
+            11 => {
+                let (ratio_bps, rest) = unpack_u16(rest)?;
+                let (due_at, rest) = unpack_option_i64(rest)?;
+                (Self::SetTerms { ratio_bps, due_at }, rest)
+            }
+            12 => {
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::LockCollateral { amount }, rest)
+            }
+            13 => {
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::ReleaseCollateral { amount }, rest)
+            }
+            14 => (Self::Liquidate, rest),
+            15 => {
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::Approve { amount }, rest)
+            }
+            16 => (Self::Revoke, rest),
+            17 => {
+                let (amount, rest) = unpack_u64(rest)?;
+                (Self::TransferFrom { amount }, rest)
+            }
+            18 => {
+                let (m, rest) = unpack_u8(rest)?;
+                let (bump, rest) = unpack_u8(rest)?;
+                (Self::InitMultisig { m, bump }, rest)
+            }
+            19 => {
+                let (logs, rest) = unpack_u8(rest)?;
+                (Self::CloseAccount { logs }, rest)
+            }
+            20 => {
+                let (count, mut rest) = unpack_u8(rest)?;
+                let mut amounts = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    let (amount, next) = unpack_u64(rest)?;
+                    amounts.push(amount);
+                    rest = next;
+                }
+                (Self::TransferBatch { amounts }, rest)
+            }
+            21 => {
+                let (bump, rest) = unpack_u8(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                let (hash, rest) = unpack_bytes32(rest)?;
+                let (expiry_slot, rest) = unpack_u64(rest)?;
+                (
+                    Self::LockHtlc {
+                        bump,
+                        amount,
+                        hash,
+                        expiry_slot,
+                    },
+                    rest,
+                )
+            }
+            22 => {
+                let (preimage, rest) = unpack_bytes32(rest)?;
+                (Self::ClaimHtlc { preimage }, rest)
+            }
+            23 => (Self::ReclaimHtlc, rest),
+            24 => {
+                let (bump, rest) = unpack_u8(rest)?;
+                let (total, rest) = unpack_u64(rest)?;
+                let (start, rest) = unpack_u64(rest)?;
+                let (cliff, rest) = unpack_u64(rest)?;
+                let (end, rest) = unpack_u64(rest)?;
+                let (revocable, rest) = match unpack_u8(rest)? {
+                    (0, rest) => (false, rest),
+                    (1, rest) => (true, rest),
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                };
+                (
+                    Self::CreateVesting {
+                        bump,
+                        total,
+                        start: start as i64,
+                        cliff: cliff as i64,
+                        end: end as i64,
+                        revocable,
+                    },
+                    rest,
+                )
+            }
+            25 => (Self::Release, rest),
+            26 => (Self::RevokeVesting, rest),
+            27 => {
+                let (bump, rest) = unpack_u8(rest)?;
+                let (deposit, rest) = unpack_u64(rest)?;
+                let (rate, rest) = unpack_u64(rest)?;
+                (
+                    Self::CreateStream {
+                        bump,
+                        deposit,
+                        rate,
+                    },
+                    rest,
+                )
+            }
+            28 => (Self::Withdraw, rest),
+            29 => (Self::CancelStream, rest),
+            30 => {
+                let (fee_bps, rest) = unpack_u16(rest)?;
+                let (max_fee, rest) = unpack_u64(rest)?;
+                (Self::SetTransferFee { fee_bps, max_fee }, rest)
+            }
+            31 => {
+                let (bump, rest) = unpack_u8(rest)?;
+                (Self::InitConfig { bump }, rest)
+            }
+            32 => (Self::Pause, rest),
+            33 => (Self::Unpause, rest),
+            34 | 35 => {
+                let (role, rest) = unpack_u8(rest)?;
+                let role = Role::from_u8(role).ok_or(ProgramError::InvalidInstructionData)?;
+                if tag == 34 {
+                    (Self::GrantRole { role }, rest)
+                } else {
+                    (Self::RevokeRole { role }, rest)
+                }
+            }
+            36 => {
+                let (reason, rest) = unpack_u16(rest)?;
+                (Self::FreezeAccount { reason }, rest)
+            }
+            37 => (Self::ThawAccount, rest),
+            38 => {
+                let (bump, rest) = unpack_u8(rest)?;
+                let (decimals, rest) = unpack_u8(rest)?;
+                let (name, rest) = unpack_string(rest)?;
+                let (symbol, rest) = unpack_string(rest)?;
+                let (uri, rest) = unpack_string(rest)?;
+                (
+                    Self::InitMetadata {
+                        bump,
+                        decimals,
+                        name,
+                        symbol,
+                        uri,
+                    },
+                    rest,
+                )
+            }
+            39 => {
+                let (name, rest) = unpack_string(rest)?;
+                let (symbol, rest) = unpack_string(rest)?;
+                let (uri, rest) = unpack_string(rest)?;
+                (Self::UpdateMetadata { name, symbol, uri }, rest)
+            }
+            40 => {
+                let (legacy_bump, rest) = unpack_u8(rest)?;
+                let (bump, rest) = unpack_u8(rest)?;
+                (Self::Migrate { legacy_bump, bump }, rest)
+            }
+            41 => {
+                let (enabled, rest) = match unpack_u8(rest)? {
+                    (0, rest) => (false, rest),
+                    (1, rest) => (true, rest),
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                };
+                (Self::SetConfigRoles { enabled }, rest)
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        expect_end(rest)?;
+        Ok(instruction)
+    }
+
+    /// Whether the instruction fails while the program is paused. Those
+    /// instructions take the config pda as their last account, after any
+    /// multisig co-signers.
+    pub fn checks_pause(&self) -> bool {
+        !matches!(
+            self,
+            Self::InitConfig { .. }
+                | Self::Pause
+                | Self::Unpause
+                | Self::GrantRole { .. }
+                | Self::RevokeRole { .. }
+        )
+    }
+
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(9);
+        match self {
+            Self::InitAccount { bump } => {
+                data.push(0);
+                data.push(*bump);
+            }
+            Self::Transfer { amount } => {
+                data.push(1);
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::MintTo { amount } => {
+                data.push(2);
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::Freeze { amount, unlock_at } => {
+                data.push(3);
+                data.extend_from_slice(&amount.to_be_bytes());
+                pack_option_i64(&mut data, *unlock_at);
+            }
+            Self::Borrow { amount } => {
+                data.push(4);
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::InitMint { supply_cap } => {
+                data.push(5);
+                pack_option_u64(&mut data, *supply_cap);
+            }
+            Self::Burn { amount } => {
+                data.push(6);
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::Thaw { amount } => {
+                data.push(7);
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::Repay { amount } => {
+                data.push(8);
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::SetRate { rate_bps } => {
+                data.push(9);
+                data.extend_from_slice(&rate_bps.to_be_bytes());
+            }
+            Self::SetCreditLimit { limit } => {
+                data.push(10);
+                data.extend_from_slice(&limit.to_be_bytes());
+            }
+            Self::SetTerms { ratio_bps, due_at } => {
+                data.push(11);
+                data.extend_from_slice(&ratio_bps.to_be_bytes());
+                pack_option_i64(&mut data, *due_at);
+            }
+            Self::LockCollateral { amount } => {
+                data.push(12);
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::ReleaseCollateral { amount } => {
+                data.push(13);
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::Liquidate => data.push(14),
+            Self::Approve { amount } => {
+                data.push(15);
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::Revoke => data.push(16),
+            Self::TransferFrom { amount } => {
+                data.push(17);
+                data.extend_from_slice(&amount.to_be_bytes());
+            }
+            Self::InitMultisig { m, bump } => {
+                data.push(18);
+                data.push(*m);
+                data.push(*bump);
+            }
+            Self::CloseAccount { logs } => {
+                data.push(19);
+                data.push(*logs);
+            }
+            Self::TransferBatch { amounts } => {
+                data.push(20);
+                data.push(amounts.len() as u8);
+                for amount in amounts {
+                    data.extend_from_slice(&amount.to_be_bytes());
+                }
+            }
+            Self::LockHtlc {
+                bump,
+                amount,
+                hash,
+                expiry_slot,
+            } => {
+                data.push(21);
+                data.push(*bump);
+                data.extend_from_slice(&amount.to_be_bytes());
+                data.extend_from_slice(hash);
+                data.extend_from_slice(&expiry_slot.to_be_bytes());
+            }
+            Self::ClaimHtlc { preimage } => {
+                data.push(22);
+                data.extend_from_slice(preimage);
+            }
+            Self::ReclaimHtlc => data.push(23),
+            Self::CreateVesting {
+                bump,
+                total,
+                start,
+                cliff,
+                end,
+                revocable,
+            } => {
+                data.push(24);
+                data.push(*bump);
+                data.extend_from_slice(&total.to_be_bytes());
+                data.extend_from_slice(&start.to_be_bytes());
+                data.extend_from_slice(&cliff.to_be_bytes());
+                data.extend_from_slice(&end.to_be_bytes());
+                data.push(*revocable as u8);
+            }
+            Self::Release => data.push(25),
+            Self::RevokeVesting => data.push(26),
+            Self::CreateStream {
+                bump,
+                deposit,
+                rate,
+            } => {
+                data.push(27);
+                data.push(*bump);
+                data.extend_from_slice(&deposit.to_be_bytes());
+                data.extend_from_slice(&rate.to_be_bytes());
+            }
+            Self::Withdraw => data.push(28),
+            Self::CancelStream => data.push(29),
+            Self::SetTransferFee { fee_bps, max_fee } => {
+                data.push(30);
+                data.extend_from_slice(&fee_bps.to_be_bytes());
+                data.extend_from_slice(&max_fee.to_be_bytes());
+            }
+            Self::InitConfig { bump } => {
+                data.push(31);
+                data.push(*bump);
+            }
+            Self::Pause => data.push(32),
+            Self::Unpause => data.push(33),
+            Self::GrantRole { role } => {
+                data.push(34);
+                data.push(*role as u8);
+            }
+            Self::RevokeRole { role } => {
+                data.push(35);
+                data.push(*role as u8);
+            }
+            Self::FreezeAccount { reason } => {
+                data.push(36);
+                data.extend_from_slice(&reason.to_be_bytes());
+            }
+            Self::ThawAccount => data.push(37),
+            Self::InitMetadata {
+                bump,
+                decimals,
+                name,
+                symbol,
+                uri,
+            } => {
+                data.push(38);
+                data.push(*bump);
+                data.push(*decimals);
+                pack_string(&mut data, name);
+                pack_string(&mut data, symbol);
+                pack_string(&mut data, uri);
+            }
+            Self::UpdateMetadata { name, symbol, uri } => {
+                data.push(39);
+                pack_string(&mut data, name);
+                pack_string(&mut data, symbol);
+                pack_string(&mut data, uri);
+            }
+            Self::Migrate { legacy_bump, bump } => {
+                data.push(40);
+                data.push(*legacy_bump);
+                data.push(*bump);
+            }
+            Self::SetConfigRoles { enabled } => {
+                data.push(41);
+                data.push(*enabled as u8);
+            }
+        }
+        data
+    }
+}
+
+/// Build an instruction of the tokens program, with the config pda as the
+/// last account when the instruction checks the pause flag.
+fn token_instruction(
+    program_id: Pubkey,
+    instruction: &TokenInstruction,
+    mut accounts: Vec<AccountMeta>,
+) -> Instruction {
+    if instruction.checks_pause() {
+        accounts.push(AccountMeta::new_readonly(
+            get_config_account(&program_id).0,
+            false,
+        ));
+    }
+    Instruction::new_with_bytes(program_id, &instruction.pack(), accounts)
+}
+
+pub fn get_token_account(owner: &Pubkey, mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[owner.as_ref(), mint.as_ref()], program_id)
+}
+
+/// Address of a token account created by the first program version.
+pub fn get_legacy_token_account(owner: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[owner.as_ref()], program_id)
+}
+
+pub fn get_config_account(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], program_id)
+}
+
+/// Address of the multisig of `signers` needing `m` of them, in that order.
+pub fn get_multisig_account(m: u8, signers: &[Pubkey], program_id: &Pubkey) -> (Pubkey, u8) {
+    let m = [m];
+    let mut seeds: Vec<&[u8]> = vec![b"multisig", &m];
+    seeds.extend(signers.iter().map(|signer| signer.as_ref()));
+    Pubkey::find_program_address(&seeds, program_id)
+}
+
+pub fn get_metadata_account(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"metadata", mint.as_ref()], program_id)
+}
+
+pub fn get_htlc_account(
+    sender: &Pubkey,
+    mint: &Pubkey,
+    recipient: &Pubkey,
+    hash: &[u8; 32],
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"htlc",
+            sender.as_ref(),
+            mint.as_ref(),
+            recipient.as_ref(),
+            hash,
+        ],
+        program_id,
+    )
+}
+
+pub fn get_vesting_account(
+    grantor: &Pubkey,
+    beneficiary: &Pubkey,
+    mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    let beneficiary_account = get_token_account(beneficiary, mint, program_id).0;
+    Pubkey::find_program_address(
+        &[b"vesting", grantor.as_ref(), beneficiary_account.as_ref()],
+        program_id,
+    )
+}
+
+pub fn get_stream_account(
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    let recipient_account = get_token_account(recipient, mint, program_id).0;
+    Pubkey::find_program_address(
+        &[b"stream", sender.as_ref(), recipient_account.as_ref()],
+        program_id,
+    )
+}
+
+pub fn init_account(program_id: Pubkey, mint: &Pubkey, owner: &Pubkey) -> Instruction {
+    let (account, bump) = get_token_account(owner, mint, &program_id);
+    token_instruction(
+        program_id,
+        &TokenInstruction::InitAccount { bump },
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(Rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Create the token account of `owner`, usually a multisig account, paid by `payer`.
+pub fn init_account_for(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    payer: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    let (account, bump) = get_token_account(owner, mint, &program_id);
+    token_instruction(
+        program_id,
+        &TokenInstruction::InitAccount { bump },
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(Rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*owner, false),
+        ],
+    )
+}
+
+/// `treasury` is the treasury token account set in the mint.
+pub fn transfer(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    from: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+    treasury: &Pubkey,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::Transfer { amount },
+        vec![
+            AccountMeta::new_readonly(*from, true),
+            AccountMeta::new(get_token_account(from, mint, &program_id).0, false),
+            AccountMeta::new(get_token_account(to, mint, &program_id).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*treasury, false),
+        ],
+    )
+}
+
+/// `mint` is a new keypair account that signs the transaction.
+pub fn init_mint(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    supply_cap: Option<u64>,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::InitMint { supply_cap },
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(*mint, true),
+            AccountMeta::new_readonly(Rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Create the mint with `authority`, usually a multisig account, paid by `payer`.
+pub fn init_mint_with_authority(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    payer: &Pubkey,
+    authority: &Pubkey,
+    supply_cap: Option<u64>,
+) -> Instruction {
+    let mut ix = init_mint(program_id, mint, payer, supply_cap);
+    ix.accounts
+        .push(AccountMeta::new_readonly(*authority, false));
+    ix
+}
+
+pub fn mint_to(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    dest: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::MintTo { amount },
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(get_token_account(dest, mint, &program_id).0, false),
+            AccountMeta::new(*mint, false),
+        ],
+    )
+}
+
+pub fn burn(program_id: Pubkey, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::Burn { amount },
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(get_token_account(owner, mint, &program_id).0, false),
+            AccountMeta::new(*mint, false),
+        ],
+    )
+}
+
+pub fn freeze(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    unlock_at: Option<i64>,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::Freeze { amount, unlock_at },
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(get_token_account(owner, mint, &program_id).0, false),
+        ],
+    )
+}
+
+/// `signer` is either `owner` or the mint authority.
+pub fn thaw(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    signer: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::Thaw { amount },
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(get_token_account(owner, mint, &program_id).0, false),
+            AccountMeta::new_readonly(Clock::id(), false),
+            AccountMeta::new_readonly(*mint, false),
+        ],
+    )
+}
+
+fn credit_accounts(
+    program_id: &Pubkey,
+    log_program_id: &Pubkey,
+    mint: &Pubkey,
+    debtor: &Pubkey,
+    creditor: &Pubkey,
+) -> Vec<AccountMeta> {
+    let debtor_account = get_token_account(debtor, mint, program_id).0;
+    let creditor_account = get_token_account(creditor, mint, program_id).0;
+    let (a1, a2, _) = log_instruction::sort_pair(&debtor_account, &creditor_account);
+    let (log_account, _) = log_instruction::get_log_account(&a1, &a2, log_program_id);
+    vec![
+        AccountMeta::new(*debtor, true),
+        AccountMeta::new(debtor_account, false),
+        AccountMeta::new(creditor_account, false),
+        AccountMeta::new(log_account, false),
+        AccountMeta::new_readonly(Rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(Clock::id(), false),
+        AccountMeta::new_readonly(*log_program_id, false),
+        AccountMeta::new_readonly(log_instruction::get_log_authority(program_id).0, false),
+    ]
+}
+
+pub fn borrow(
+    program_id: Pubkey,
+    log_program_id: Pubkey,
+    mint: &Pubkey,
+    borrower: &Pubkey,
+    lender: &Pubkey,
+    amount: u64,
+    treasury: &Pubkey,
+) -> Instruction {
+    let mut accounts = credit_accounts(&program_id, &log_program_id, mint, borrower, lender);
+    accounts.push(AccountMeta::new_readonly(*lender, true));
+    accounts.push(AccountMeta::new_readonly(*mint, false));
+    accounts.push(AccountMeta::new(*treasury, false));
+    token_instruction(program_id, &TokenInstruction::Borrow { amount }, accounts)
+}
+
+pub fn repay(
+    program_id: Pubkey,
+    log_program_id: Pubkey,
+    mint: &Pubkey,
+    debtor: &Pubkey,
+    creditor: &Pubkey,
+    amount: u64,
+    treasury: &Pubkey,
+) -> Instruction {
+    let mut accounts = credit_accounts(&program_id, &log_program_id, mint, debtor, creditor);
+    accounts.push(AccountMeta::new_readonly(*mint, false));
+    accounts.push(AccountMeta::new(*treasury, false));
+    token_instruction(program_id, &TokenInstruction::Repay { amount }, accounts)
+}
+
+pub fn set_rate(
+    program_id: Pubkey,
+    log_program_id: Pubkey,
+    mint: &Pubkey,
+    lender: &Pubkey,
+    borrower: &Pubkey,
+    rate_bps: u16,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::SetRate { rate_bps },
+        credit_accounts(&program_id, &log_program_id, mint, lender, borrower),
+    )
+}
+
+pub fn set_credit_limit(
+    program_id: Pubkey,
+    log_program_id: Pubkey,
+    mint: &Pubkey,
+    lender: &Pubkey,
+    borrower: &Pubkey,
+    limit: u64,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::SetCreditLimit { limit },
+        credit_accounts(&program_id, &log_program_id, mint, lender, borrower),
+    )
+}
+
+pub fn set_terms(
+    program_id: Pubkey,
+    log_program_id: Pubkey,
+    mint: &Pubkey,
+    lender: &Pubkey,
+    borrower: &Pubkey,
+    ratio_bps: u16,
+    due_at: Option<i64>,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::SetTerms { ratio_bps, due_at },
+        credit_accounts(&program_id, &log_program_id, mint, lender, borrower),
+    )
+}
+
+pub fn lock_collateral(
+    program_id: Pubkey,
+    log_program_id: Pubkey,
+    mint: &Pubkey,
+    borrower: &Pubkey,
+    lender: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::LockCollateral { amount },
+        credit_accounts(&program_id, &log_program_id, mint, borrower, lender),
+    )
+}
+
+pub fn release_collateral(
+    program_id: Pubkey,
+    log_program_id: Pubkey,
+    mint: &Pubkey,
+    borrower: &Pubkey,
+    lender: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::ReleaseCollateral { amount },
+        credit_accounts(&program_id, &log_program_id, mint, borrower, lender),
+    )
+}
+
+/// `caller` can be anyone, it only pays the transaction.
+pub fn liquidate(
+    program_id: Pubkey,
+    log_program_id: Pubkey,
+    mint: &Pubkey,
+    caller: &Pubkey,
+    borrower: &Pubkey,
+    lender: &Pubkey,
+    treasury: &Pubkey,
+) -> Instruction {
+    let mut accounts = credit_accounts(&program_id, &log_program_id, mint, borrower, lender);
+    accounts[0] = AccountMeta::new(*caller, true);
+    accounts.push(AccountMeta::new_readonly(*mint, false));
+    accounts.push(AccountMeta::new(*treasury, false));
+    token_instruction(program_id, &TokenInstruction::Liquidate, accounts)
+}
+
+pub fn approve(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::Approve { amount },
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(get_token_account(owner, mint, &program_id).0, false),
+            AccountMeta::new_readonly(*delegate, false),
+        ],
+    )
+}
+
+pub fn revoke(program_id: Pubkey, mint: &Pubkey, owner: &Pubkey) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::Revoke,
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(get_token_account(owner, mint, &program_id).0, false),
+        ],
+    )
+}
+
+/// Move `amount` from the token account of `owner` to the one of `to`, signed by `delegate`.
+pub fn transfer_from(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    delegate: &Pubkey,
+    owner: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+    treasury: &Pubkey,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::TransferFrom { amount },
+        vec![
+            AccountMeta::new_readonly(*delegate, true),
+            AccountMeta::new(get_token_account(owner, mint, &program_id).0, false),
+            AccountMeta::new(get_token_account(to, mint, &program_id).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*treasury, false),
+        ],
+    )
+}
+
+pub fn init_multisig(program_id: Pubkey, payer: &Pubkey, m: u8, signers: &[Pubkey]) -> Instruction {
+    let (multisig, bump) = get_multisig_account(m, signers, &program_id);
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(multisig, false),
+        AccountMeta::new_readonly(Rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(
+        signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, false)),
+    );
+    token_instruction(
+        program_id,
+        &TokenInstruction::InitMultisig { m, bump },
+        accounts,
+    )
+}
+
+/// Turn an instruction built with a multisig account as its signer into one
+/// signed by the given co-signers of the multisig. The first co-signer pays
+/// any rent in place of the multisig.
+pub fn with_multisig(mut ix: Instruction, signers: &[Pubkey]) -> Instruction {
+    ix.accounts[0].is_signer = false;
+    //the co-signers go before the config pda, which stays last
+    let config = get_config_account(&ix.program_id).0;
+    let at = match ix.accounts.last() {
+        Some(last) if last.pubkey == config => ix.accounts.len() - 1,
+        _ => ix.accounts.len(),
+    };
+    ix.accounts.splice(
+        at..at,
+        signers.iter().enumerate().map(|(i, signer)| AccountMeta {
+            pubkey: *signer,
+            is_signer: true,
+            is_writable: i == 0,
+        }),
+    );
+    ix
+}
+
+/// `counterparties` are the owners `owner` has borrowed from or lent to, at
+/// most 255.
+pub fn close_account(
+    program_id: Pubkey,
+    log_program_id: Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    destination: &Pubkey,
+    counterparties: &[Pubkey],
+) -> Instruction {
+    let account = get_token_account(owner, mint, &program_id).0;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(account, false),
+        AccountMeta::new(*destination, false),
+    ];
+    accounts.extend(counterparties.iter().map(|counterparty| {
+        let other = get_token_account(counterparty, mint, &program_id).0;
+        let (a1, a2, _) = log_instruction::sort_pair(&account, &other);
+        AccountMeta::new_readonly(
+            log_instruction::get_log_account(&a1, &a2, &log_program_id).0,
+            false,
+        )
+    }));
+    token_instruction(
+        program_id,
+        &TokenInstruction::CloseAccount {
+            logs: counterparties.len() as u8,
+        },
+        accounts,
+    )
+}
+
+/// Pay every `(owner, amount)` of `payments` from the token account of `from`,
+/// at most 255 payments per instruction.
+pub fn transfer_batch(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    from: &Pubkey,
+    payments: &[(Pubkey, u64)],
+    treasury: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*from, true),
+        AccountMeta::new(get_token_account(from, mint, &program_id).0, false),
+    ];
+    accounts.extend(
+        payments
+            .iter()
+            .map(|(to, _)| AccountMeta::new(get_token_account(to, mint, &program_id).0, false)),
+    );
+    accounts.push(AccountMeta::new_readonly(*mint, false));
+    accounts.push(AccountMeta::new(*treasury, false));
+    let amounts = payments.iter().map(|(_, amount)| *amount).collect();
+    token_instruction(
+        program_id,
+        &TokenInstruction::TransferBatch { amounts },
+        accounts,
+    )
+}
+
+pub fn lock_htlc(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+    hash: [u8; 32],
+    expiry_slot: u64,
+) -> Instruction {
+    let (escrow, bump) = get_htlc_account(sender, mint, recipient, &hash, &program_id);
+    token_instruction(
+        program_id,
+        &TokenInstruction::LockHtlc {
+            bump,
+            amount,
+            hash,
+            expiry_slot,
+        },
+        vec![
+            AccountMeta::new(*sender, true),
+            AccountMeta::new(get_token_account(sender, mint, &program_id).0, false),
+            AccountMeta::new_readonly(get_token_account(recipient, mint, &program_id).0, false),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new_readonly(Rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(Clock::id(), false),
+        ],
+    )
+}
+
+pub fn claim_htlc(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    caller: &Pubkey,
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    preimage: [u8; 32],
+    treasury: &Pubkey,
+) -> Instruction {
+    let hash = solana_program::hash::hash(&preimage).to_bytes();
+    token_instruction(
+        program_id,
+        &TokenInstruction::ClaimHtlc { preimage },
+        vec![
+            AccountMeta::new_readonly(*caller, true),
+            AccountMeta::new(
+                get_htlc_account(sender, mint, recipient, &hash, &program_id).0,
+                false,
+            ),
+            AccountMeta::new(get_token_account(recipient, mint, &program_id).0, false),
+            AccountMeta::new(*sender, false),
+            AccountMeta::new_readonly(Clock::id(), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*treasury, false),
+        ],
+    )
+}
+
+pub fn reclaim_htlc(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    hash: &[u8; 32],
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::ReclaimHtlc,
+        vec![
+            AccountMeta::new(*sender, true),
+            AccountMeta::new(
+                get_htlc_account(sender, mint, recipient, hash, &program_id).0,
+                false,
+            ),
+            AccountMeta::new(get_token_account(sender, mint, &program_id).0, false),
+            AccountMeta::new_readonly(Clock::id(), false),
+            AccountMeta::new_readonly(get_token_account(recipient, mint, &program_id).0, false),
+        ],
+    )
+}
+
+/// The schedule is `(start, cliff, end)` unix timestamps.
+pub fn create_vesting(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    grantor: &Pubkey,
+    beneficiary: &Pubkey,
+    total: u64,
+    schedule: (i64, i64, i64),
+    revocable: bool,
+) -> Instruction {
+    let (vesting, bump) = get_vesting_account(grantor, beneficiary, mint, &program_id);
+    let (start, cliff, end) = schedule;
+    token_instruction(
+        program_id,
+        &TokenInstruction::CreateVesting {
+            bump,
+            total,
+            start,
+            cliff,
+            end,
+            revocable,
+        },
+        vec![
+            AccountMeta::new(*grantor, true),
+            AccountMeta::new(get_token_account(grantor, mint, &program_id).0, false),
+            AccountMeta::new_readonly(get_token_account(beneficiary, mint, &program_id).0, false),
+            AccountMeta::new(vesting, false),
+            AccountMeta::new_readonly(Rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn release(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    caller: &Pubkey,
+    grantor: &Pubkey,
+    beneficiary: &Pubkey,
+    treasury: &Pubkey,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::Release,
+        vec![
+            AccountMeta::new_readonly(*caller, true),
+            AccountMeta::new(
+                get_vesting_account(grantor, beneficiary, mint, &program_id).0,
+                false,
+            ),
+            AccountMeta::new(get_token_account(beneficiary, mint, &program_id).0, false),
+            AccountMeta::new(*grantor, false),
+            AccountMeta::new_readonly(Clock::id(), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*treasury, false),
+        ],
+    )
+}
+
+pub fn revoke_vesting(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    grantor: &Pubkey,
+    beneficiary: &Pubkey,
+    treasury: &Pubkey,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::RevokeVesting,
+        vec![
+            AccountMeta::new(*grantor, true),
+            AccountMeta::new(
+                get_vesting_account(grantor, beneficiary, mint, &program_id).0,
+                false,
+            ),
+            AccountMeta::new(get_token_account(beneficiary, mint, &program_id).0, false),
+            AccountMeta::new(get_token_account(grantor, mint, &program_id).0, false),
+            AccountMeta::new_readonly(Clock::id(), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*treasury, false),
+        ],
+    )
+}
+
+pub fn create_stream(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    deposit: u64,
+    rate: u64,
+) -> Instruction {
+    let (stream, bump) = get_stream_account(sender, recipient, mint, &program_id);
+    token_instruction(
+        program_id,
+        &TokenInstruction::CreateStream {
+            bump,
+            deposit,
+            rate,
+        },
+        vec![
+            AccountMeta::new(*sender, true),
+            AccountMeta::new(get_token_account(sender, mint, &program_id).0, false),
+            AccountMeta::new_readonly(get_token_account(recipient, mint, &program_id).0, false),
+            AccountMeta::new(stream, false),
+            AccountMeta::new_readonly(Rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(Clock::id(), false),
+        ],
+    )
+}
+
+pub fn withdraw(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    recipient: &Pubkey,
+    sender: &Pubkey,
+    treasury: &Pubkey,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::Withdraw,
+        vec![
+            AccountMeta::new_readonly(*recipient, true),
+            AccountMeta::new(
+                get_stream_account(sender, recipient, mint, &program_id).0,
+                false,
+            ),
+            AccountMeta::new(get_token_account(recipient, mint, &program_id).0, false),
+            AccountMeta::new(*sender, false),
+            AccountMeta::new_readonly(Clock::id(), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*treasury, false),
+        ],
+    )
+}
+
+/// `signer` is either `sender` or `recipient`.
+pub fn cancel_stream(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    signer: &Pubkey,
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    treasury: &Pubkey,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::CancelStream,
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(
+                get_stream_account(sender, recipient, mint, &program_id).0,
+                false,
+            ),
+            AccountMeta::new(get_token_account(recipient, mint, &program_id).0, false),
+            AccountMeta::new(get_token_account(sender, mint, &program_id).0, false),
+            AccountMeta::new(*sender, false),
+            AccountMeta::new_readonly(Clock::id(), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*treasury, false),
+        ],
+    )
+}
+
+/// `treasury` is a token account of `mint`, usually of the authority.
+pub fn set_transfer_fee(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    fee_bps: u16,
+    max_fee: u64,
+    treasury: &Pubkey,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::SetTransferFee { fee_bps, max_fee },
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*treasury, false),
+        ],
+    )
+}
+
+pub fn set_config_roles(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    enabled: bool,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::SetConfigRoles { enabled },
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*mint, false),
+        ],
+    )
+}
+
+/// `admin` defaults to `payer` when `None`.
+/// `payer` must be the upgrade authority of the program.
+pub fn init_config(program_id: Pubkey, payer: &Pubkey, admin: Option<&Pubkey>) -> Instruction {
+    let (config, bump) = get_config_account(&program_id);
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(config, false),
+        AccountMeta::new_readonly(Rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(
+            bpf_loader_upgradeable::get_program_data_address(&program_id),
+            false,
+        ),
+    ];
+    if let Some(admin) = admin {
+        accounts.push(AccountMeta::new_readonly(*admin, false));
+    }
+    token_instruction(program_id, &TokenInstruction::InitConfig { bump }, accounts)
+}
+
+/// `pauser` is the admin while the pauser role is unassigned.
+pub fn pause(program_id: Pubkey, pauser: &Pubkey) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::Pause,
+        vec![
+            AccountMeta::new_readonly(*pauser, true),
+            AccountMeta::new(get_config_account(&program_id).0, false),
+        ],
+    )
+}
+
+pub fn unpause(program_id: Pubkey, pauser: &Pubkey) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::Unpause,
+        vec![
+            AccountMeta::new_readonly(*pauser, true),
+            AccountMeta::new(get_config_account(&program_id).0, false),
+        ],
+    )
+}
+
+pub fn grant_role(program_id: Pubkey, admin: &Pubkey, role: Role, holder: &Pubkey) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::GrantRole { role },
+        vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(get_config_account(&program_id).0, false),
+            AccountMeta::new_readonly(*holder, false),
+        ],
+    )
+}
+
+pub fn revoke_role(program_id: Pubkey, admin: &Pubkey, role: Role) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::RevokeRole { role },
+        vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(get_config_account(&program_id).0, false),
+        ],
+    )
+}
+
+/// `freezer` is the mint authority unless the mint uses config roles.
+pub fn freeze_account(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    freezer: &Pubkey,
+    owner: &Pubkey,
+    reason: u16,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::FreezeAccount { reason },
+        vec![
+            AccountMeta::new_readonly(*freezer, true),
+            AccountMeta::new(get_token_account(owner, mint, &program_id).0, false),
+            AccountMeta::new_readonly(*mint, false),
+        ],
+    )
+}
+
+pub fn thaw_account(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    freezer: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::ThawAccount,
+        vec![
+            AccountMeta::new_readonly(*freezer, true),
+            AccountMeta::new(get_token_account(owner, mint, &program_id).0, false),
+            AccountMeta::new_readonly(*mint, false),
+        ],
+    )
+}
+
+pub fn init_metadata(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    decimals: u8,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Instruction {
+    let (metadata, bump) = get_metadata_account(mint, &program_id);
+    token_instruction(
+        program_id,
+        &TokenInstruction::InitMetadata {
+            bump,
+            decimals,
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+        },
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(Rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Replace the metadata strings, `new_authority` takes over the updates.
+pub fn update_metadata(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    new_authority: &Pubkey,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Instruction {
+    token_instruction(
+        program_id,
+        &TokenInstruction::UpdateMetadata {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+        },
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(get_metadata_account(mint, &program_id).0, false),
+            AccountMeta::new_readonly(*new_authority, false),
+        ],
+    )
+}
+
+pub fn migrate(
+    program_id: Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    let (legacy, legacy_bump) = get_legacy_token_account(owner, &program_id);
+    let (account, bump) = get_token_account(owner, mint, &program_id);
+    token_instruction(
+        program_id,
+        &TokenInstruction::Migrate { legacy_bump, bump },
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(legacy, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*owner, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(Rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(account, false),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::token_instructions;
+
+    #[test]
+    fn round_trip() {
+        for instruction in token_instructions() {
+            let data = instruction.pack();
+            assert_eq!(TokenInstruction::unpack(&data), Ok(instruction));
+        }
+    }
+
+    #[test]
+    fn truncated_input() {
+        for instruction in token_instructions() {
+            let data = instruction.pack();
+            for len in 0..data.len() {
+                assert_eq!(
+                    TokenInstruction::unpack(&data[..len]),
+                    Err(ProgramError::InvalidInstructionData),
+                    "{:?} cut to {} bytes",
+                    instruction,
+                    len
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn trailing_bytes() {
+        for instruction in token_instructions() {
+            let mut data = instruction.pack();
+            data.push(0);
+            assert_eq!(
+                TokenInstruction::unpack(&data),
+                Err(ProgramError::InvalidInstructionData),
+                "{:?}",
+                instruction
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_tag() {
+        for tag in [42, u8::MAX] {
+            assert_eq!(
+                TokenInstruction::unpack(&[tag]),
+                Err(ProgramError::InvalidInstructionData)
+            );
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Instruction layouts shared by the tokens program, the log program and the cli.
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+pub mod error;
+pub mod event;
+#[cfg(test)]
+mod fixtures;
+pub mod instruction;
+pub mod log_instruction;
+pub mod log_state;
+pub mod state;
+
+include!(concat!(env!("OUT_DIR"), "/program_ids.rs"));
+
+fn unpack_u8(input: &[u8]) -> Result<(u8, &[u8]), ProgramError> {
+    let (&value, rest) = input
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    Ok((value, rest))
+}
+
+fn unpack_u16(input: &[u8]) -> Result<(u16, &[u8]), ProgramError> {
+    if input.len() < 2 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (value, rest) = input.split_at(2);
+    Ok((u16::from_be_bytes([value[0], value[1]]), rest))
+}
+
+fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+    if input.len() < 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (value, rest) = input.split_at(8);
+    let mut raw = [0u8; 8];
+    raw.copy_from_slice(value);
+    Ok((u64::from_be_bytes(raw), rest))
+}
+
+fn unpack_bytes32(input: &[u8]) -> Result<([u8; 32], &[u8]), ProgramError> {
+    if input.len() < 32 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (value, rest) = input.split_at(32);
+    let mut raw = [0u8; 32];
+    raw.copy_from_slice(value);
+    Ok((raw, rest))
+}
+
+fn unpack_option_u64(input: &[u8]) -> Result<(Option<u64>, &[u8]), ProgramError> {
+    let (flag, rest) = unpack_u8(input)?;
+    let (value, rest) = unpack_u64(rest)?;
+    match flag {
+        0 => Ok((None, rest)),
+        1 => Ok((Some(value), rest)),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn pack_option_u64(data: &mut Vec<u8>, value: Option<u64>) {
+    data.push(value.is_some() as u8);
+    data.extend_from_slice(&value.unwrap_or_default().to_be_bytes());
+}
+
+fn unpack_option_i64(input: &[u8]) -> Result<(Option<i64>, &[u8]), ProgramError> {
+    let (value, rest) = unpack_option_u64(input)?;
+    Ok((value.map(|v| v as i64), rest))
+}
+
+fn pack_option_i64(data: &mut Vec<u8>, value: Option<i64>) {
+    pack_option_u64(data, value.map(|v| v as u64));
+}
+
+fn unpack_string(input: &[u8]) -> Result<(String, &[u8]), ProgramError> {
+    let (len, rest) = unpack_u8(input)?;
+    if rest.len() < len as usize {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (value, rest) = rest.split_at(len as usize);
+    let value =
+        String::from_utf8(value.to_vec()).map_err(|_| ProgramError::InvalidInstructionData)?;
+    Ok((value, rest))
+}
+
+/// Write `value` after its length byte, strings longer than 255 bytes are cut
+/// and rejected by the program as too long for any metadata field.
+fn pack_string(data: &mut Vec<u8>, value: &str) {
+    let len = value.len().min(u8::MAX as usize);
+    data.push(len as u8);
+    data.extend_from_slice(&value.as_bytes()[..len]);
+}
+
+fn expect_end(rest: &[u8]) -> Result<(), ProgramError> {
+    if !rest.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok(())
+}
@@ -0,0 +1,501 @@
+use solana_program::{
+    clock::Clock,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::SysvarId,
+};
+
+use crate::{expect_end, unpack_u16, unpack_u64, unpack_u8};
+
+pub const BOOK_SIZE: usize = 32 * 40;
+pub const LOG_DATA_SIZE: usize = 9 + 64 + 20 + 16 + 36;
+/// Size of log accounts created before interest was tracked, grown on the next write.
+pub const LEGACY_LOG_DATA_SIZE: usize = 9 + 64;
+
+/// Instructions understood by the log program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogInstruction {
+    /// Create the log pda of a pair.
+    ///
+    /// 0. `[signer, writable]` payer
+    /// 1. `[writable]` log pda `[a1, a2]`
+    /// 2. `[]` a1, the greater key of the pair
+    /// 3. `[]` a2
+    /// 4. `[]` rent sysvar
+    /// 5. `[]` system program
+    InitLog { bump: u8 },
+    /// Record a credit between a pair, creating the log pda when it is empty.
+    /// `direction` 0 means a1 is the creditor, 1 means a2 is the creditor.
+    /// `interest` is the accrued interest paid back by this entry.
+    ///
+    /// 0. `[signer, writable]` payer
+    /// 1. `[writable]` log pda `[a1, a2]`
+    /// 2. `[]` a1, the greater key of the pair
+    /// 3. `[]` a2
+    /// 4. `[]` rent sysvar
+    /// 5. `[]` system program
+    /// 6. `[]` clock sysvar
+    /// 7. `[signer]` log authority pda `[b"log"]` of the tokens program
+    Record {
+        direction: u8,
+        amount: u64,
+        interest: u64,
+        bump: u8,
+    },
+    /// Set the annual rate in basis points charged when the `direction` side lends.
+    ///
+    /// Accounts are the same as `Record`.
+    SetRate {
+        direction: u8,
+        rate_bps: u16,
+        bump: u8,
+    },
+    /// Create a record book account.
+    ///
+    /// 0. `[signer, writable]` payer
+    /// 1. `[signer, writable]` book account
+    /// 2. `[]` rent sysvar
+    /// 3. `[]` system program
+    InitBook,
+    /// Accrue the interest of a pair up to now, anyone can call it.
+    ///
+    /// 0. `[writable]` log pda
+    /// 1. `[]` clock sysvar
+    Accrue,
+    /// Set how much the `direction` side lends the other side at most.
+    ///
+    /// Accounts are the same as `Record`.
+    SetLimit { direction: u8, limit: u64, bump: u8 },
+    /// Set the collateral ratio in basis points and the due timestamp, 0 for
+    /// none, of the credit lent by the `direction` side. While that side is
+    /// owed the terms can only be loosened.
+    ///
+    /// Accounts are the same as `Record`.
+    SetTerms {
+        direction: u8,
+        ratio_bps: u16,
+        due_at: i64,
+        bump: u8,
+    },
+    /// Set the collateral posted to the `direction` side.
+    ///
+    /// Accounts are the same as `Record`.
+    SetCollateral {
+        direction: u8,
+        collateral: u64,
+        bump: u8,
+    },
+}
+
+fn unpack_direction(input: &[u8]) -> Result<(u8, &[u8]), ProgramError> {
+    let (direction, rest) = unpack_u8(input)?;
+    if direction > 1 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok((direction, rest))
+}
+
+impl LogInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = unpack_u8(input)?;
+        let (instruction, rest) = match tag {
+            0 => {
+                let (bump, rest) = unpack_u8(rest)?;
+                (Self::InitLog { bump }, rest)
+            }
+            1 => {
+                let (direction, rest) = unpack_direction(rest)?;
+                let (amount, rest) = unpack_u64(rest)?;
+                let (interest, rest) = unpack_u64(rest)?;
+                let (bump, rest) = unpack_u8(rest)?;
+                (
+                    Self::Record {
+                        direction,
+                        amount,
+                        interest,
+                        bump,
+                    },
+                    rest,
+                )
+            }
+            2 => (Self::InitBook, rest),
+            3 => {
+                let (direction, rest) = unpack_direction(rest)?;
+                let (rate_bps, rest) = unpack_u16(rest)?;
+                let (bump, rest) = unpack_u8(rest)?;
+                (
+                    Self::SetRate {
+                        direction,
+                        rate_bps,
+                        bump,
+                    },
+                    rest,
+                )
+            }
+            4 => (Self::Accrue, rest),
+            5 => {
+                let (direction, rest) = unpack_direction(rest)?;
+                let (limit, rest) = unpack_u64(rest)?;
+                let (bump, rest) = unpack_u8(rest)?;
+                (
+                    Self::SetLimit {
+                        direction,
+                        limit,
+                        bump,
+                    },
+                    rest,
+                )
+            }
+            6 => {
+                let (direction, rest) = unpack_direction(rest)?;
+                let (ratio_bps, rest) = unpack_u16(rest)?;
+                let (due_at, rest) = unpack_u64(rest)?;
+                let (bump, rest) = unpack_u8(rest)?;
+                (
+                    Self::SetTerms {
+                        direction,
+                        ratio_bps,
+                        due_at: due_at as i64,
+                        bump,
+                    },
+                    rest,
+                )
+            }
+            7 => {
+                let (direction, rest) = unpack_direction(rest)?;
+                let (collateral, rest) = unpack_u64(rest)?;
+                let (bump, rest) = unpack_u8(rest)?;
+                (
+                    Self::SetCollateral {
+                        direction,
+                        collateral,
+                        bump,
+                    },
+                    rest,
+                )
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        expect_end(rest)?;
+        Ok(instruction)
+    }
+
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(19);
+        match self {
+            Self::InitLog { bump } => {
+                data.push(0);
+                data.push(*bump);
+            }
+            Self::Record {
+                direction,
+                amount,
+                interest,
+                bump,
+            } => {
+                data.push(1);
+                data.push(*direction);
+                data.extend_from_slice(&amount.to_be_bytes());
+                data.extend_from_slice(&interest.to_be_bytes());
+                data.push(*bump);
+            }
+            Self::InitBook => data.push(2),
+            Self::SetRate {
+                direction,
+                rate_bps,
+                bump,
+            } => {
+                data.push(3);
+                data.push(*direction);
+                data.extend_from_slice(&rate_bps.to_be_bytes());
+                data.push(*bump);
+            }
+            Self::Accrue => data.push(4),
+            Self::SetLimit {
+                direction,
+                limit,
+                bump,
+            } => {
+                data.push(5);
+                data.push(*direction);
+                data.extend_from_slice(&limit.to_be_bytes());
+                data.push(*bump);
+            }
+            Self::SetTerms {
+                direction,
+                ratio_bps,
+                due_at,
+                bump,
+            } => {
+                data.push(6);
+                data.push(*direction);
+                data.extend_from_slice(&ratio_bps.to_be_bytes());
+                data.extend_from_slice(&due_at.to_be_bytes());
+                data.push(*bump);
+            }
+            Self::SetCollateral {
+                direction,
+                collateral,
+                bump,
+            } => {
+                data.push(7);
+                data.push(*direction);
+                data.extend_from_slice(&collateral.to_be_bytes());
+                data.push(*bump);
+            }
+        }
+        data
+    }
+}
+
+/// Order a pair so the greater key comes first, the third value tells if it was swapped.
+pub fn sort_pair(x: &Pubkey, y: &Pubkey) -> (Pubkey, Pubkey, bool) {
+    if x.as_ref() >= y.as_ref() {
+        (*x, *y, false)
+    } else {
+        (*y, *x, true)
+    }
+}
+
+/// Pda of the tokens program that signs every write to a log pda.
+pub fn get_log_authority(tokens_program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"log"], tokens_program_id)
+}
+
+pub fn get_log_account(a1: &Pubkey, a2: &Pubkey, log_program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[a1.as_ref(), a2.as_ref()], log_program_id)
+}
+
+fn pair_accounts(
+    payer: &Pubkey,
+    a1: &Pubkey,
+    a2: &Pubkey,
+    log_account: Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(log_account, false),
+        AccountMeta::new_readonly(*a1, false),
+        AccountMeta::new_readonly(*a2, false),
+        AccountMeta::new_readonly(Rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]
+}
+
+pub fn init_log(log_program_id: Pubkey, payer: &Pubkey, x: &Pubkey, y: &Pubkey) -> Instruction {
+    let (a1, a2, _) = sort_pair(x, y);
+    let (log_account, bump) = get_log_account(&a1, &a2, &log_program_id);
+    Instruction::new_with_bytes(
+        log_program_id,
+        &LogInstruction::InitLog { bump }.pack(),
+        pair_accounts(payer, &a1, &a2, log_account),
+    )
+}
+
+fn authorized_pair_accounts(
+    tokens_program_id: &Pubkey,
+    payer: &Pubkey,
+    a1: &Pubkey,
+    a2: &Pubkey,
+    log_account: Pubkey,
+) -> Vec<AccountMeta> {
+    let mut accounts = pair_accounts(payer, a1, a2, log_account);
+    accounts.push(AccountMeta::new_readonly(Clock::id(), false));
+    accounts.push(AccountMeta::new_readonly(
+        get_log_authority(tokens_program_id).0,
+        true,
+    ));
+    accounts
+}
+
+pub fn record(
+    log_program_id: Pubkey,
+    tokens_program_id: &Pubkey,
+    payer: &Pubkey,
+    creditor: &Pubkey,
+    debtor: &Pubkey,
+    amount: u64,
+    interest: u64,
+) -> Instruction {
+    let (a1, a2, swapped) = sort_pair(creditor, debtor);
+    let (log_account, bump) = get_log_account(&a1, &a2, &log_program_id);
+    Instruction::new_with_bytes(
+        log_program_id,
+        &LogInstruction::Record {
+            direction: swapped as u8,
+            amount,
+            interest,
+            bump,
+        }
+        .pack(),
+        authorized_pair_accounts(tokens_program_id, payer, &a1, &a2, log_account),
+    )
+}
+
+pub fn set_rate(
+    log_program_id: Pubkey,
+    tokens_program_id: &Pubkey,
+    payer: &Pubkey,
+    lender: &Pubkey,
+    borrower: &Pubkey,
+    rate_bps: u16,
+) -> Instruction {
+    let (a1, a2, swapped) = sort_pair(lender, borrower);
+    let (log_account, bump) = get_log_account(&a1, &a2, &log_program_id);
+    Instruction::new_with_bytes(
+        log_program_id,
+        &LogInstruction::SetRate {
+            direction: swapped as u8,
+            rate_bps,
+            bump,
+        }
+        .pack(),
+        authorized_pair_accounts(tokens_program_id, payer, &a1, &a2, log_account),
+    )
+}
+
+pub fn set_limit(
+    log_program_id: Pubkey,
+    tokens_program_id: &Pubkey,
+    payer: &Pubkey,
+    lender: &Pubkey,
+    borrower: &Pubkey,
+    limit: u64,
+) -> Instruction {
+    let (a1, a2, swapped) = sort_pair(lender, borrower);
+    let (log_account, bump) = get_log_account(&a1, &a2, &log_program_id);
+    Instruction::new_with_bytes(
+        log_program_id,
+        &LogInstruction::SetLimit {
+            direction: swapped as u8,
+            limit,
+            bump,
+        }
+        .pack(),
+        authorized_pair_accounts(tokens_program_id, payer, &a1, &a2, log_account),
+    )
+}
+
+pub fn set_terms(
+    log_program_id: Pubkey,
+    tokens_program_id: &Pubkey,
+    payer: &Pubkey,
+    lender: &Pubkey,
+    borrower: &Pubkey,
+    ratio_bps: u16,
+    due_at: i64,
+) -> Instruction {
+    let (a1, a2, swapped) = sort_pair(lender, borrower);
+    let (log_account, bump) = get_log_account(&a1, &a2, &log_program_id);
+    Instruction::new_with_bytes(
+        log_program_id,
+        &LogInstruction::SetTerms {
+            direction: swapped as u8,
+            ratio_bps,
+            due_at,
+            bump,
+        }
+        .pack(),
+        authorized_pair_accounts(tokens_program_id, payer, &a1, &a2, log_account),
+    )
+}
+
+pub fn set_collateral(
+    log_program_id: Pubkey,
+    tokens_program_id: &Pubkey,
+    payer: &Pubkey,
+    lender: &Pubkey,
+    borrower: &Pubkey,
+    collateral: u64,
+) -> Instruction {
+    let (a1, a2, swapped) = sort_pair(lender, borrower);
+    let (log_account, bump) = get_log_account(&a1, &a2, &log_program_id);
+    Instruction::new_with_bytes(
+        log_program_id,
+        &LogInstruction::SetCollateral {
+            direction: swapped as u8,
+            collateral,
+            bump,
+        }
+        .pack(),
+        authorized_pair_accounts(tokens_program_id, payer, &a1, &a2, log_account),
+    )
+}
+
+pub fn accrue(log_program_id: Pubkey, x: &Pubkey, y: &Pubkey) -> Instruction {
+    let (a1, a2, _) = sort_pair(x, y);
+    let (log_account, _) = get_log_account(&a1, &a2, &log_program_id);
+    Instruction::new_with_bytes(
+        log_program_id,
+        &LogInstruction::Accrue.pack(),
+        vec![
+            AccountMeta::new(log_account, false),
+            AccountMeta::new_readonly(Clock::id(), false),
+        ],
+    )
+}
+
+pub fn init_book(log_program_id: Pubkey, payer: &Pubkey, book: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        log_program_id,
+        &LogInstruction::InitBook.pack(),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*book, true),
+            AccountMeta::new_readonly(Rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::log_instructions;
+
+    #[test]
+    fn round_trip() {
+        for instruction in log_instructions() {
+            let data = instruction.pack();
+            assert_eq!(LogInstruction::unpack(&data), Ok(instruction));
+        }
+    }
+
+    #[test]
+    fn truncated_input() {
+        for instruction in log_instructions() {
+            let data = instruction.pack();
+            for len in 0..data.len() {
+                assert_eq!(
+                    LogInstruction::unpack(&data[..len]),
+                    Err(ProgramError::InvalidInstructionData),
+                    "{:?} cut to {} bytes",
+                    instruction,
+                    len
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_tag_and_direction() {
+        assert_eq!(
+            LogInstruction::unpack(&[8]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        let mut data = LogInstruction::SetLimit {
+            direction: 0,
+            limit: 1,
+            bump: 255,
+        }
+        .pack();
+        data[1] = 2;
+        assert_eq!(
+            LogInstruction::unpack(&data),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
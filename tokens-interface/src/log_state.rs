@@ -0,0 +1,266 @@
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::log_instruction::{LEGACY_LOG_DATA_SIZE, LOG_DATA_SIZE};
+
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Net credit between a pair stored in the log pda `[a1, a2]`.
+///
+/// layout: 0 direction, 1~9 principal, 9~41 a1, 41~73 a2, 73~75 annual rate in
+/// basis points when a1 lends, 75~77 when a2 lends, 77~85 last accrual timestamp,
+/// 85~93 accrued interest, 93~101 credit limit when a1 lends, 101~109 when a2 lends,
+/// 109~111 collateral ratio in basis points when a1 lends, 111~113 when a2 lends,
+/// 113~121 due timestamp when a1 lends, 121~129 when a2 lends, 129~137 collateral
+/// posted to a1, 137~145 to a2.
+/// direction 0 means a1 is the creditor, 1 means a2 is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CreditLog {
+    pub direction: u8,
+    pub amount: u64,
+    pub a1: Pubkey,
+    pub a2: Pubkey,
+    /// Annual rate in basis points charged by the creditor, indexed like `direction`.
+    pub rates: [u16; 2],
+    pub last_accrual: i64,
+    pub interest: u64,
+    /// Most the side lends the other side, indexed like `direction`.
+    pub limits: [u64; 2],
+    /// Collateral the side asks for in basis points of the debt, indexed like `direction`.
+    pub ratios: [u16; 2],
+    /// Unix timestamp the debt to the side is due, 0 when it has no due date.
+    pub due: [i64; 2],
+    /// Frozen balance the debtor locked for the side, indexed like `direction`.
+    pub collaterals: [u64; 2],
+}
+
+impl CreditLog {
+    pub const LEN: usize = LOG_DATA_SIZE;
+
+    /// Accounts smaller than `LEN` were written by an older layout, the missing
+    /// fields read as zero.
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < LEGACY_LOG_DATA_SIZE || src.len() > Self::LEN || src[0] > 1 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut data = [0u8; Self::LEN];
+        data[..src.len()].copy_from_slice(src);
+        let mut raw = [0u8; 8];
+        raw.copy_from_slice(&data[1..9]);
+        let amount = u64::from_be_bytes(raw);
+        raw.copy_from_slice(&data[77..85]);
+        let last_accrual = i64::from_be_bytes(raw);
+        raw.copy_from_slice(&data[85..93]);
+        let interest = u64::from_be_bytes(raw);
+        raw.copy_from_slice(&data[93..101]);
+        let limit_a1 = u64::from_be_bytes(raw);
+        raw.copy_from_slice(&data[101..109]);
+        let limit_a2 = u64::from_be_bytes(raw);
+        raw.copy_from_slice(&data[113..121]);
+        let due_a1 = i64::from_be_bytes(raw);
+        raw.copy_from_slice(&data[121..129]);
+        let due_a2 = i64::from_be_bytes(raw);
+        raw.copy_from_slice(&data[129..137]);
+        let collateral_a1 = u64::from_be_bytes(raw);
+        raw.copy_from_slice(&data[137..145]);
+        let collateral_a2 = u64::from_be_bytes(raw);
+        let mut a1 = [0u8; 32];
+        a1.copy_from_slice(&data[9..41]);
+        let mut a2 = [0u8; 32];
+        a2.copy_from_slice(&data[41..73]);
+        Ok(CreditLog {
+            direction: data[0],
+            amount,
+            a1: Pubkey::new_from_array(a1),
+            a2: Pubkey::new_from_array(a2),
+            rates: [
+                u16::from_be_bytes([data[73], data[74]]),
+                u16::from_be_bytes([data[75], data[76]]),
+            ],
+            last_accrual,
+            interest,
+            limits: [limit_a1, limit_a2],
+            ratios: [
+                u16::from_be_bytes([data[109], data[110]]),
+                u16::from_be_bytes([data[111], data[112]]),
+            ],
+            due: [due_a1, due_a2],
+            collaterals: [collateral_a1, collateral_a2],
+        })
+    }
+
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        if dst.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        dst[0] = self.direction;
+        dst[1..9].copy_from_slice(&self.amount.to_be_bytes());
+        dst[9..41].copy_from_slice(self.a1.as_ref());
+        dst[41..73].copy_from_slice(self.a2.as_ref());
+        dst[73..75].copy_from_slice(&self.rates[0].to_be_bytes());
+        dst[75..77].copy_from_slice(&self.rates[1].to_be_bytes());
+        dst[77..85].copy_from_slice(&self.last_accrual.to_be_bytes());
+        dst[85..93].copy_from_slice(&self.interest.to_be_bytes());
+        dst[93..101].copy_from_slice(&self.limits[0].to_be_bytes());
+        dst[101..109].copy_from_slice(&self.limits[1].to_be_bytes());
+        dst[109..111].copy_from_slice(&self.ratios[0].to_be_bytes());
+        dst[111..113].copy_from_slice(&self.ratios[1].to_be_bytes());
+        dst[113..121].copy_from_slice(&self.due[0].to_be_bytes());
+        dst[121..129].copy_from_slice(&self.due[1].to_be_bytes());
+        dst[129..137].copy_from_slice(&self.collaterals[0].to_be_bytes());
+        dst[137..145].copy_from_slice(&self.collaterals[1].to_be_bytes());
+        Ok(())
+    }
+
+    pub fn creditor(&self) -> Pubkey {
+        if self.direction == 0 {
+            self.a1
+        } else {
+            self.a2
+        }
+    }
+
+    pub fn debtor(&self) -> Pubkey {
+        if self.direction == 0 {
+            self.a2
+        } else {
+            self.a1
+        }
+    }
+
+    /// Index of `key` in `rates`, `limits` and the other per side fields.
+    pub fn side(&self, key: &Pubkey) -> usize {
+        if self.a1 == *key {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Amount `debtor` still owes to `creditor`.
+    pub fn owed(&self, creditor: &Pubkey, debtor: &Pubkey) -> u64 {
+        if self.creditor() == *creditor && self.debtor() == *debtor {
+            self.amount
+        } else {
+            0
+        }
+    }
+
+    /// Principal and accrued interest `debtor` still owes to `creditor`.
+    pub fn debt(&self, creditor: &Pubkey, debtor: &Pubkey) -> u64 {
+        if self.creditor() == *creditor && self.debtor() == *debtor {
+            self.amount.saturating_add(self.interest)
+        } else {
+            0
+        }
+    }
+
+    /// Whether the collateral posted to `side` covers `debt` at its ratio.
+    pub fn is_covered(&self, side: usize, debt: u64) -> bool {
+        self.collaterals[side] as u128 * 10_000 >= debt as u128 * self.ratios[side] as u128
+    }
+
+    /// Whether the debt to `side` has a due date that has passed at `now`.
+    pub fn is_past_due(&self, side: usize, now: i64) -> bool {
+        self.due[side] != 0 && now >= self.due[side]
+    }
+
+    /// Add the simple interest on the principal since the last accrual.
+    /// The timestamp only moves when interest was added, so frequent calls
+    /// do not round the interest away.
+    pub fn accrue(&mut self, now: i64) {
+        if now <= self.last_accrual {
+            return;
+        }
+        let rate = self.rates[self.direction as usize];
+        if self.amount == 0 || rate == 0 {
+            self.last_accrual = now;
+            return;
+        }
+        let elapsed = (now - self.last_accrual) as u128;
+        let interest =
+            self.amount as u128 * rate as u128 * elapsed / (10_000 * SECONDS_PER_YEAR as u128);
+        if interest == 0 {
+            return;
+        }
+        self.interest = self
+            .interest
+            .saturating_add(interest.min(u64::MAX as u128) as u64);
+        self.last_accrual = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::credit_log;
+
+    #[test]
+    fn accrue_a_year() {
+        let mut log = credit_log(1_000_000, 1_000);
+        log.accrue(1_000 + SECONDS_PER_YEAR as i64);
+        assert_eq!(log.interest, 100_000);
+        assert_eq!(log.last_accrual, 1_000 + SECONDS_PER_YEAR as i64);
+    }
+
+    #[test]
+    fn accrue_at_the_creditor_rate() {
+        let mut log = CreditLog {
+            direction: 1,
+            rates: [1_000, 500],
+            ..credit_log(1_000_000, 0)
+        };
+        log.accrue(1_000 + SECONDS_PER_YEAR as i64);
+        assert_eq!(log.interest, 50_000);
+    }
+
+    #[test]
+    fn accrue_never_goes_back() {
+        let mut log = credit_log(1_000_000, 1_000);
+        log.accrue(1_000);
+        log.accrue(500);
+        assert_eq!(log.interest, 0);
+        assert_eq!(log.last_accrual, 1_000);
+    }
+
+    #[test]
+    fn accrue_without_interest_moves_the_timestamp() {
+        let mut no_rate = credit_log(1_000_000, 0);
+        no_rate.accrue(2_000);
+        assert_eq!((no_rate.interest, no_rate.last_accrual), (0, 2_000));
+        let mut no_debt = credit_log(0, 1_000);
+        no_debt.accrue(2_000);
+        assert_eq!((no_debt.interest, no_debt.last_accrual), (0, 2_000));
+    }
+
+    #[test]
+    fn accrue_keeps_rounded_away_interest() {
+        //1 token a year at 100%, under 1 after a minute
+        let mut log = credit_log(1, 10_000);
+        log.accrue(1_060);
+        assert_eq!((log.interest, log.last_accrual), (0, 1_000));
+        log.accrue(1_000 + SECONDS_PER_YEAR as i64);
+        assert_eq!(log.interest, 1);
+        assert_eq!(log.last_accrual, 1_000 + SECONDS_PER_YEAR as i64);
+    }
+
+    #[test]
+    fn accrue_saturates() {
+        let mut log = CreditLog {
+            interest: u64::MAX - 1,
+            ..credit_log(u64::MAX, u16::MAX)
+        };
+        log.accrue(1_000 + SECONDS_PER_YEAR as i64);
+        assert_eq!(log.interest, u64::MAX);
+    }
+
+    #[test]
+    fn past_due() {
+        let mut log = credit_log(1, 0);
+        assert!(!log.is_past_due(0, i64::MAX));
+        log.due = [2_000, 0];
+        assert!(!log.is_past_due(0, 1_999));
+        assert!(log.is_past_due(0, 2_000));
+        assert!(log.is_past_due(0, 2_001));
+        assert!(!log.is_past_due(1, 2_001));
+    }
+}
@@ -0,0 +1,908 @@
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+/// First byte of every account owned by the tokens program.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountType {
+    Uninitialized = 0,
+    Token = 1,
+    Mint = 2,
+    Multisig = 3,
+    Htlc = 4,
+    Vesting = 5,
+    Stream = 6,
+    Config = 7,
+    Metadata = 8,
+}
+
+/// Check the length, account type and version of a program owned account.
+fn check_header(
+    src: &[u8],
+    len: usize,
+    account_type: AccountType,
+    version: u8,
+) -> Result<(), ProgramError> {
+    if src.len() != len {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if src[0] == AccountType::Uninitialized as u8 {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    if src[0] != account_type as u8 || src[1] != version {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+fn write_header(
+    dst: &mut [u8],
+    len: usize,
+    account_type: AccountType,
+    version: u8,
+) -> Result<(), ProgramError> {
+    if dst.len() != len {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    dst[0] = account_type as u8;
+    dst[1] = version;
+    Ok(())
+}
+
+fn read_u64(src: &[u8], start: usize) -> u64 {
+    let mut raw = [0u8; 8];
+    raw.copy_from_slice(&src[start..start + 8]);
+    u64::from_be_bytes(raw)
+}
+
+fn read_pubkey(src: &[u8], start: usize) -> Pubkey {
+    let mut raw = [0u8; 32];
+    raw.copy_from_slice(&src[start..start + 32]);
+    Pubkey::new_from_array(raw)
+}
+
+/// Token account stored in the pda `[owner, mint]`.
+///
+/// layout: 0 account type, 1 version, 2~34 owner, 34~42 balance, 42~50 frozen,
+/// 50~58 borrowed, 58~66 debit, 66~74 unlock timestamp of the frozen bucket,
+/// 74~82 collateral, 82 delegate flag, 83~115 delegate, 115~123 allowance,
+/// 123 account freeze flag, 124~126 freeze reason, 126~158 mint, 158~160 reserved.
+/// Numbers are big endian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TokenAccount {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub frozen: u64,
+    pub borrowed: u64,
+    pub debit: u64,
+    /// Unix timestamp before which the owner can not thaw, 0 when not locked.
+    pub unlock_at: i64,
+    /// Part of the frozen bucket locked as collateral for credit lines.
+    pub collateral: u64,
+    /// Key allowed to move up to `allowance` of the balance with `TransferFrom`.
+    pub delegate: Option<Pubkey>,
+    pub allowance: u64,
+    /// Reason code of a compliance freeze of the whole account by the freezer.
+    /// While set, nothing moves in or out of the account.
+    pub freeze_reason: Option<u16>,
+    /// Mint the balance is denominated in, balances only move between
+    /// accounts of the same mint.
+    pub mint: Pubkey,
+}
+
+impl TokenAccount {
+    pub const LEN: usize = 160;
+    pub const VERSION: u8 = 1;
+
+    pub fn new(owner: Pubkey, mint: Pubkey) -> Self {
+        TokenAccount {
+            owner,
+            mint,
+            ..Default::default()
+        }
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        check_header(src, Self::LEN, AccountType::Token, Self::VERSION)?;
+        Ok(TokenAccount {
+            owner: read_pubkey(src, 2),
+            balance: read_u64(src, 34),
+            frozen: read_u64(src, 42),
+            borrowed: read_u64(src, 50),
+            debit: read_u64(src, 58),
+            unlock_at: read_u64(src, 66) as i64,
+            collateral: read_u64(src, 74),
+            delegate: match src[82] {
+                0 => None,
+                1 => Some(read_pubkey(src, 83)),
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+            allowance: read_u64(src, 115),
+            freeze_reason: match src[123] {
+                0 => None,
+                1 => Some(u16::from_be_bytes([src[124], src[125]])),
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+            mint: read_pubkey(src, 126),
+        })
+    }
+
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        write_header(dst, Self::LEN, AccountType::Token, Self::VERSION)?;
+        dst[2..34].copy_from_slice(self.owner.as_ref());
+        dst[34..42].copy_from_slice(&self.balance.to_be_bytes());
+        dst[42..50].copy_from_slice(&self.frozen.to_be_bytes());
+        dst[50..58].copy_from_slice(&self.borrowed.to_be_bytes());
+        dst[58..66].copy_from_slice(&self.debit.to_be_bytes());
+        dst[66..74].copy_from_slice(&self.unlock_at.to_be_bytes());
+        dst[74..82].copy_from_slice(&self.collateral.to_be_bytes());
+        dst[82] = self.delegate.is_some() as u8;
+        dst[83..115].copy_from_slice(self.delegate.unwrap_or_default().as_ref());
+        dst[115..123].copy_from_slice(&self.allowance.to_be_bytes());
+        dst[123] = self.freeze_reason.is_some() as u8;
+        dst[124..126].copy_from_slice(&self.freeze_reason.unwrap_or_default().to_be_bytes());
+        dst[126..158].copy_from_slice(self.mint.as_ref());
+        Ok(())
+    }
+}
+
+/// Token account of the first program version, stored in the pda `[owner]`
+/// without account type or version.
+///
+/// layout: 0~8 balance, 8~16 frozen, 16~24 borrowed, 24~32 debit, 32~80 unused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LegacyTokenAccount {
+    pub balance: u64,
+    pub frozen: u64,
+    pub borrowed: u64,
+    pub debit: u64,
+}
+
+impl LegacyTokenAccount {
+    pub const LEN: usize = 80;
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(LegacyTokenAccount {
+            balance: read_u64(src, 0),
+            frozen: read_u64(src, 8),
+            borrowed: read_u64(src, 16),
+            debit: read_u64(src, 24),
+        })
+    }
+
+    /// The same balances as a current token account of `owner` for `mint`.
+    pub fn migrate(&self, owner: Pubkey, mint: Pubkey) -> TokenAccount {
+        TokenAccount {
+            balance: self.balance,
+            frozen: self.frozen,
+            borrowed: self.borrowed,
+            debit: self.debit,
+            ..TokenAccount::new(owner, mint)
+        }
+    }
+}
+
+/// Mint configuration stored in a keypair account, its address is the mint.
+///
+/// layout: 0 account type, 1 version, 2~34 authority, 34~42 supply,
+/// 42 supply cap flag, 43~51 supply cap, 51~53 transfer fee in basis points,
+/// 53~61 maximum fee per transfer, 61~93 treasury token account, 93 config roles
+/// flag, 94~128 reserved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Mint {
+    pub authority: Pubkey,
+    pub supply: u64,
+    pub supply_cap: Option<u64>,
+    pub fee_bps: u16,
+    pub max_fee: u64,
+    /// Token account the transfer fees are withheld into.
+    pub treasury: Pubkey,
+    /// Whether the minter, freezer, fee manager and upgrader of the config act
+    /// for the mint. Off by default, which leaves them to the mint authority.
+    pub config_roles: bool,
+}
+
+impl Mint {
+    pub const LEN: usize = 128;
+    pub const VERSION: u8 = 1;
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        check_header(src, Self::LEN, AccountType::Mint, Self::VERSION)?;
+        Ok(Mint {
+            authority: read_pubkey(src, 2),
+            supply: read_u64(src, 34),
+            supply_cap: match src[42] {
+                0 => None,
+                1 => Some(read_u64(src, 43)),
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+            fee_bps: u16::from_be_bytes([src[51], src[52]]),
+            max_fee: read_u64(src, 53),
+            treasury: read_pubkey(src, 61),
+            config_roles: match src[93] {
+                0 => false,
+                1 => true,
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+        })
+    }
+
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        write_header(dst, Self::LEN, AccountType::Mint, Self::VERSION)?;
+        dst[2..34].copy_from_slice(self.authority.as_ref());
+        dst[34..42].copy_from_slice(&self.supply.to_be_bytes());
+        dst[42] = self.supply_cap.is_some() as u8;
+        dst[43..51].copy_from_slice(&self.supply_cap.unwrap_or_default().to_be_bytes());
+        dst[51..53].copy_from_slice(&self.fee_bps.to_be_bytes());
+        dst[53..61].copy_from_slice(&self.max_fee.to_be_bytes());
+        dst[61..93].copy_from_slice(self.treasury.as_ref());
+        dst[93] = self.config_roles as u8;
+        Ok(())
+    }
+
+    /// The key allowed to act for `role` on the mint: the role holder of
+    /// `config` once the mint uses config roles, else the mint authority.
+    pub fn role_authority(&self, config: &Config, role: Role) -> Pubkey {
+        if self.config_roles {
+            config.authority(role, self.authority)
+        } else {
+            self.authority
+        }
+    }
+
+    /// Fee withheld from a transfer of `amount`, at most `max_fee`. Balance
+    /// moved into an escrow, vesting grant or stream pays it when it leaves
+    /// them, not on the way in.
+    pub fn transfer_fee(&self, amount: u64) -> u64 {
+        let fee = amount as u128 * self.fee_bps as u128 / 10_000;
+        fee.min(self.max_fee as u128) as u64
+    }
+}
+
+/// Display metadata of a mint stored in the pda `[b"metadata", mint]`.
+///
+/// layout: 0 account type, 1 version, 2~34 mint, 34~66 update authority,
+/// 66 decimals, 67 name length, 68~100 name, 100 symbol length, 101~111 symbol,
+/// 111 uri length, 112~312 uri, 312~320 reserved.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Metadata {
+    pub mint: Pubkey,
+    pub update_authority: Pubkey,
+    /// Digits of an amount after the decimal point, fixed at creation.
+    pub decimals: u8,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+impl Metadata {
+    pub const LEN: usize = 320;
+    pub const VERSION: u8 = 1;
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const MAX_SYMBOL_LEN: usize = 10;
+    pub const MAX_URI_LEN: usize = 200;
+    /// Most decimals whose scale still fits a u64.
+    pub const MAX_DECIMALS: u8 = 19;
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        check_header(src, Self::LEN, AccountType::Metadata, Self::VERSION)?;
+        Ok(Metadata {
+            mint: read_pubkey(src, 2),
+            update_authority: read_pubkey(src, 34),
+            decimals: src[66],
+            name: read_string(src, 67, Self::MAX_NAME_LEN)?,
+            symbol: read_string(src, 100, Self::MAX_SYMBOL_LEN)?,
+            uri: read_string(src, 111, Self::MAX_URI_LEN)?,
+        })
+    }
+
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        if !self.fits() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        write_header(dst, Self::LEN, AccountType::Metadata, Self::VERSION)?;
+        dst[2..34].copy_from_slice(self.mint.as_ref());
+        dst[34..66].copy_from_slice(self.update_authority.as_ref());
+        dst[66] = self.decimals;
+        write_string(dst, 67, Self::MAX_NAME_LEN, &self.name);
+        write_string(dst, 100, Self::MAX_SYMBOL_LEN, &self.symbol);
+        write_string(dst, 111, Self::MAX_URI_LEN, &self.uri);
+        Ok(())
+    }
+
+    /// Whether the strings fit their slots and the decimals a u64.
+    pub fn fits(&self) -> bool {
+        self.name.len() <= Self::MAX_NAME_LEN
+            && self.symbol.len() <= Self::MAX_SYMBOL_LEN
+            && self.uri.len() <= Self::MAX_URI_LEN
+            && self.decimals <= Self::MAX_DECIMALS
+    }
+
+    /// Show a raw amount with the decimals and symbol, `1250` as `12.50 CRD`.
+    pub fn format_amount(&self, amount: u64) -> String {
+        let mut text = amount.to_string();
+        let decimals = self.decimals as usize;
+        if decimals > 0 {
+            if text.len() <= decimals {
+                text = format!("{}{}", "0".repeat(decimals + 1 - text.len()), text);
+            }
+            text.insert(text.len() - decimals, '.');
+        }
+        if !self.symbol.is_empty() {
+            text.push(' ');
+            text.push_str(&self.symbol);
+        }
+        text
+    }
+
+    /// Read an amount written with at most `decimals` digits after the point,
+    /// `12.5` as `1250`. `None` when it is not a number or does not fit.
+    pub fn parse_amount(&self, text: &str) -> Option<u64> {
+        let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+        let decimals = self.decimals as usize;
+        if fraction.len() > decimals
+            || (whole.is_empty() && fraction.is_empty())
+            || !whole
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+        let digits = format!("{whole}{fraction:0<decimals$}");
+        digits.parse().ok()
+    }
+}
+
+fn read_string(src: &[u8], start: usize, max_len: usize) -> Result<String, ProgramError> {
+    let len = src[start] as usize;
+    if len > max_len {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    String::from_utf8(src[start + 1..start + 1 + len].to_vec())
+        .map_err(|_| ProgramError::InvalidAccountData)
+}
+
+fn write_string(dst: &mut [u8], start: usize, max_len: usize, value: &str) {
+    dst[start] = value.len() as u8;
+    dst[start + 1..start + 1 + max_len].fill(0);
+    dst[start + 1..start + 1 + value.len()].copy_from_slice(value.as_bytes());
+}
+
+/// M-of-N signer set stored in the pda `[b"multisig", m, signer keys]`, it can
+/// own token accounts and hold the mint authority.
+///
+/// layout: 0 account type, 1 version, 2 m, 3 n, 4~356 signer keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Multisig {
+    /// Signatures needed to act for the multisig.
+    pub m: u8,
+    pub n: u8,
+    pub signers: [Pubkey; Multisig::MAX_SIGNERS],
+}
+
+impl Multisig {
+    pub const MAX_SIGNERS: usize = 11;
+    pub const LEN: usize = 4 + 32 * Self::MAX_SIGNERS;
+    pub const VERSION: u8 = 1;
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        check_header(src, Self::LEN, AccountType::Multisig, Self::VERSION)?;
+        let mut multisig = Multisig {
+            m: src[2],
+            n: src[3],
+            ..Default::default()
+        };
+        if multisig.n as usize > Self::MAX_SIGNERS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        for (i, key) in multisig.signers.iter_mut().enumerate() {
+            *key = read_pubkey(src, 4 + 32 * i);
+        }
+        Ok(multisig)
+    }
+
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        write_header(dst, Self::LEN, AccountType::Multisig, Self::VERSION)?;
+        dst[2] = self.m;
+        dst[3] = self.n;
+        for (i, key) in self.signers.iter().enumerate() {
+            dst[4 + 32 * i..36 + 32 * i].copy_from_slice(key.as_ref());
+        }
+        Ok(())
+    }
+
+    pub fn keys(&self) -> &[Pubkey] {
+        &self.signers[..self.n as usize]
+    }
+}
+
+/// Hash time locked escrow stored in the pda `[b"htlc", sender, mint, recipient, hash]`.
+///
+/// layout: 0 account type, 1 version, 2~34 sender, 34~66 recipient token account,
+/// 66~74 amount, 74~106 sha-256 hash lock, 106~114 expiry slot, 114~128 reserved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Htlc {
+    /// Owner that locked the amount, it gets the rent back on close.
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub hash: [u8; 32],
+    /// The recipient can claim before this slot, the sender can reclaim from it.
+    pub expiry_slot: u64,
+}
+
+impl Htlc {
+    pub const LEN: usize = 128;
+    pub const VERSION: u8 = 1;
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        check_header(src, Self::LEN, AccountType::Htlc, Self::VERSION)?;
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&src[74..106]);
+        Ok(Htlc {
+            sender: read_pubkey(src, 2),
+            recipient: read_pubkey(src, 34),
+            amount: read_u64(src, 66),
+            hash,
+            expiry_slot: read_u64(src, 106),
+        })
+    }
+
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        write_header(dst, Self::LEN, AccountType::Htlc, Self::VERSION)?;
+        dst[2..34].copy_from_slice(self.sender.as_ref());
+        dst[34..66].copy_from_slice(self.recipient.as_ref());
+        dst[66..74].copy_from_slice(&self.amount.to_be_bytes());
+        dst[74..106].copy_from_slice(&self.hash);
+        dst[106..114].copy_from_slice(&self.expiry_slot.to_be_bytes());
+        Ok(())
+    }
+}
+
+/// Vesting grant stored in the pda `[b"vesting", grantor, beneficiary token account]`.
+///
+/// layout: 0 account type, 1 version, 2~34 grantor, 34~66 beneficiary token account,
+/// 66~74 total, 74~82 released, 82~90 start, 90~98 cliff, 98~106 end timestamps,
+/// 106 revocable flag, 107~128 reserved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Vesting {
+    pub grantor: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total: u64,
+    pub released: u64,
+    pub start: i64,
+    /// Nothing vests before the cliff, then the linear part since `start` vests at once.
+    pub cliff: i64,
+    pub end: i64,
+    /// Whether the grantor can take back the unvested part.
+    pub revocable: bool,
+}
+
+impl Vesting {
+    pub const LEN: usize = 128;
+    pub const VERSION: u8 = 1;
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        check_header(src, Self::LEN, AccountType::Vesting, Self::VERSION)?;
+        Ok(Vesting {
+            grantor: read_pubkey(src, 2),
+            beneficiary: read_pubkey(src, 34),
+            total: read_u64(src, 66),
+            released: read_u64(src, 74),
+            start: read_u64(src, 82) as i64,
+            cliff: read_u64(src, 90) as i64,
+            end: read_u64(src, 98) as i64,
+            revocable: match src[106] {
+                0 => false,
+                1 => true,
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+        })
+    }
+
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        write_header(dst, Self::LEN, AccountType::Vesting, Self::VERSION)?;
+        dst[2..34].copy_from_slice(self.grantor.as_ref());
+        dst[34..66].copy_from_slice(self.beneficiary.as_ref());
+        dst[66..74].copy_from_slice(&self.total.to_be_bytes());
+        dst[74..82].copy_from_slice(&self.released.to_be_bytes());
+        dst[82..90].copy_from_slice(&self.start.to_be_bytes());
+        dst[90..98].copy_from_slice(&self.cliff.to_be_bytes());
+        dst[98..106].copy_from_slice(&self.end.to_be_bytes());
+        dst[106] = self.revocable as u8;
+        Ok(())
+    }
+
+    /// Amount vested at `now`, linear from `start` to `end` once the cliff passed.
+    pub fn vested(&self, now: i64) -> u64 {
+        if now < self.cliff {
+            0
+        } else if now >= self.end {
+            self.total
+        } else {
+            let elapsed = (now - self.start) as u128;
+            let duration = (self.end - self.start) as u128;
+            (self.total as u128 * elapsed / duration) as u64
+        }
+    }
+}
+
+/// Payment stream stored in the pda `[b"stream", sender, recipient token account]`.
+///
+/// layout: 0 account type, 1 version, 2~34 sender, 34~66 recipient token account,
+/// 66~74 deposit, 74~82 withdrawn, 82~90 rate per second, 90~98 start timestamp,
+/// 98~128 reserved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stream {
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub deposit: u64,
+    pub withdrawn: u64,
+    pub rate: u64,
+    pub start: i64,
+}
+
+impl Stream {
+    pub const LEN: usize = 128;
+    pub const VERSION: u8 = 1;
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        check_header(src, Self::LEN, AccountType::Stream, Self::VERSION)?;
+        Ok(Stream {
+            sender: read_pubkey(src, 2),
+            recipient: read_pubkey(src, 34),
+            deposit: read_u64(src, 66),
+            withdrawn: read_u64(src, 74),
+            rate: read_u64(src, 82),
+            start: read_u64(src, 90) as i64,
+        })
+    }
+
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        write_header(dst, Self::LEN, AccountType::Stream, Self::VERSION)?;
+        dst[2..34].copy_from_slice(self.sender.as_ref());
+        dst[34..66].copy_from_slice(self.recipient.as_ref());
+        dst[66..74].copy_from_slice(&self.deposit.to_be_bytes());
+        dst[74..82].copy_from_slice(&self.withdrawn.to_be_bytes());
+        dst[82..90].copy_from_slice(&self.rate.to_be_bytes());
+        dst[90..98].copy_from_slice(&self.start.to_be_bytes());
+        Ok(())
+    }
+
+    /// Amount streamed to the recipient up to `now`, at most the deposit.
+    pub fn streamed(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.start).max(0) as u128;
+        (self.rate as u128 * elapsed).min(self.deposit as u128) as u64
+    }
+
+    /// Amount the recipient can withdraw at `now`.
+    pub fn available(&self, now: i64) -> u64 {
+        self.streamed(now) - self.withdrawn
+    }
+
+    /// Split of the remaining deposit when the stream is cancelled at `now`:
+    /// the part still owed to the recipient and the part refunded to the sender.
+    pub fn cancel_split(&self, now: i64) -> (u64, u64) {
+        let streamed = self.streamed(now);
+        (streamed - self.withdrawn, self.deposit - streamed)
+    }
+}
+
+/// Roles the admin can hand out in the config. An unassigned role falls
+/// back to whoever held the permission before roles existed. The mint roles
+/// only apply to mints that enabled `Mint::config_roles`.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Mints new supply, instead of the mint authority.
+    Minter = 0,
+    /// Thaws frozen balances of other owners, instead of the mint authority.
+    Freezer = 1,
+    /// Pauses and unpauses the program, instead of the admin.
+    Pauser = 2,
+    /// Sets the transfer fee, instead of the mint authority.
+    FeeManager = 3,
+    /// Migrates legacy token accounts into the mint, instead of the mint
+    /// authority.
+    Upgrader = 4,
+}
+
+impl Role {
+    pub const ALL: [Role; 5] = [
+        Role::Minter,
+        Role::Freezer,
+        Role::Pauser,
+        Role::FeeManager,
+        Role::Upgrader,
+    ];
+
+    pub fn from_u8(role: u8) -> Option<Self> {
+        Self::ALL.get(role as usize).copied()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Role::Minter => "minter",
+            Role::Freezer => "freezer",
+            Role::Pauser => "pauser",
+            Role::FeeManager => "fee-manager",
+            Role::Upgrader => "upgrader",
+        }
+    }
+}
+
+/// Program configuration stored in the pda `[b"config"]`.
+///
+/// layout: 0 account type, 1 version, 2~34 admin, 34 paused flag,
+/// 35~195 role holders in `Role` order, 195~256 reserved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Config {
+    pub admin: Pubkey,
+    /// Every instruction but the config ones fails while set.
+    pub paused: bool,
+    /// Holder of each role, `Pubkey::default()` when unassigned.
+    pub roles: [Pubkey; 5],
+}
+
+impl Config {
+    pub const LEN: usize = 256;
+    pub const VERSION: u8 = 2;
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        check_header(src, Self::LEN, AccountType::Config, Self::VERSION)?;
+        let mut roles = [Pubkey::default(); 5];
+        for (i, role) in roles.iter_mut().enumerate() {
+            *role = read_pubkey(src, 35 + i * 32);
+        }
+        Ok(Config {
+            admin: read_pubkey(src, 2),
+            paused: match src[34] {
+                0 => false,
+                1 => true,
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+            roles,
+        })
+    }
+
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        write_header(dst, Self::LEN, AccountType::Config, Self::VERSION)?;
+        for (i, role) in self.roles.iter().enumerate() {
+            dst[35 + i * 32..67 + i * 32].copy_from_slice(role.as_ref());
+        }
+        dst[2..34].copy_from_slice(self.admin.as_ref());
+        dst[34] = self.paused as u8;
+        Ok(())
+    }
+
+    pub fn role(&self, role: Role) -> Option<Pubkey> {
+        Some(self.roles[role as usize]).filter(|holder| *holder != Pubkey::default())
+    }
+
+    /// The key allowed to act for `role`: its holder, else `fallback`.
+    pub fn authority(&self, role: Role, fallback: Pubkey) -> Pubkey {
+        self.role(role).unwrap_or(fallback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{metadata, mint, stream, token_account, vesting};
+
+    #[test]
+    fn token_account_round_trip() {
+        for state in [
+            token_account(),
+            TokenAccount::new(Pubkey::new_unique(), Pubkey::new_unique()),
+        ] {
+            let mut data = [0u8; TokenAccount::LEN];
+            state.pack(&mut data).unwrap();
+            assert_eq!(data[0], AccountType::Token as u8);
+            assert_eq!(data[1], TokenAccount::VERSION);
+            assert_eq!(TokenAccount::unpack(&data), Ok(state));
+        }
+    }
+
+    #[test]
+    fn token_account_header() {
+        let mut data = [0u8; TokenAccount::LEN];
+        assert_eq!(
+            TokenAccount::unpack(&data),
+            Err(ProgramError::UninitializedAccount)
+        );
+        token_account().pack(&mut data).unwrap();
+        let mut wrong_version = data;
+        wrong_version[1] = TokenAccount::VERSION + 1;
+        assert_eq!(
+            TokenAccount::unpack(&wrong_version),
+            Err(ProgramError::InvalidAccountData)
+        );
+        let mut wrong_type = data;
+        wrong_type[0] = AccountType::Mint as u8;
+        assert_eq!(
+            TokenAccount::unpack(&wrong_type),
+            Err(ProgramError::InvalidAccountData)
+        );
+        let mut bad_flag = data;
+        bad_flag[82] = 2;
+        assert_eq!(
+            TokenAccount::unpack(&bad_flag),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn token_account_length() {
+        let mut short = [0u8; TokenAccount::LEN - 1];
+        assert_eq!(
+            token_account().pack(&mut short),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            TokenAccount::unpack(&short),
+            Err(ProgramError::InvalidAccountData)
+        );
+        let mut data = [0u8; TokenAccount::LEN];
+        token_account().pack(&mut data).unwrap();
+        let mut long = data.to_vec();
+        long.push(0);
+        assert_eq!(
+            TokenAccount::unpack(&long),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn nothing_vests_before_the_cliff() {
+        let vesting = vesting();
+        assert_eq!(vesting.vested(i64::MIN), 0);
+        assert_eq!(vesting.vested(100), 0);
+        assert_eq!(vesting.vested(199), 0);
+    }
+
+    #[test]
+    fn cliff_releases_the_linear_part_since_start() {
+        let vesting = vesting();
+        assert_eq!(vesting.vested(200), 250);
+        assert_eq!(vesting.vested(300), 500);
+        assert_eq!(vesting.vested(499), 997);
+    }
+
+    #[test]
+    fn everything_vests_at_the_end() {
+        let vesting = vesting();
+        assert_eq!(vesting.vested(500), 1_000);
+        assert_eq!(vesting.vested(i64::MAX), 1_000);
+        let instant = Vesting {
+            cliff: 100,
+            end: 100,
+            ..vesting
+        };
+        assert_eq!(instant.vested(99), 0);
+        assert_eq!(instant.vested(100), 1_000);
+    }
+
+    #[test]
+    fn streamed_is_capped_by_the_deposit() {
+        let stream = stream();
+        assert_eq!(stream.streamed(i64::MIN), 0);
+        assert_eq!(stream.streamed(100), 0);
+        assert_eq!(stream.streamed(150), 500);
+        assert_eq!(stream.streamed(200), 1_000);
+        assert_eq!(stream.streamed(i64::MAX), 1_000);
+        let fast = Stream {
+            rate: u64::MAX,
+            ..stream
+        };
+        assert_eq!(fast.streamed(i64::MAX), 1_000);
+    }
+
+    #[test]
+    fn available_leaves_out_withdrawals() {
+        let stream = Stream {
+            withdrawn: 300,
+            ..stream()
+        };
+        assert_eq!(stream.available(130), 0);
+        assert_eq!(stream.available(150), 200);
+        assert_eq!(stream.available(300), 700);
+    }
+
+    #[test]
+    fn cancel_splits_the_remaining_deposit() {
+        let stream = Stream {
+            withdrawn: 200,
+            ..stream()
+        };
+        assert_eq!(stream.cancel_split(120), (0, 800));
+        assert_eq!(stream.cancel_split(150), (300, 500));
+        assert_eq!(stream.cancel_split(250), (800, 0));
+        for now in [120, 150, 175, 250] {
+            let (paid, refunded) = stream.cancel_split(now);
+            assert_eq!(paid + refunded, stream.deposit - stream.withdrawn);
+        }
+    }
+
+    #[test]
+    fn transfer_fee_rounds_down() {
+        let mint = mint(25, u64::MAX);
+        assert_eq!(mint.transfer_fee(0), 0);
+        assert_eq!(mint.transfer_fee(399), 0);
+        assert_eq!(mint.transfer_fee(400), 1);
+        assert_eq!(mint.transfer_fee(799), 1);
+        assert_eq!(mint.transfer_fee(1_000_000), 2_500);
+    }
+
+    #[test]
+    fn transfer_fee_is_capped_by_max_fee() {
+        let capped = mint(100, 50);
+        assert_eq!(capped.transfer_fee(4_000), 40);
+        assert_eq!(capped.transfer_fee(5_000), 50);
+        assert_eq!(capped.transfer_fee(u64::MAX), 50);
+        assert_eq!(mint(100, 0).transfer_fee(u64::MAX), 0);
+    }
+
+    #[test]
+    fn transfer_fee_does_not_overflow() {
+        assert_eq!(mint(10_000, u64::MAX).transfer_fee(u64::MAX), u64::MAX);
+        assert_eq!(mint(0, u64::MAX).transfer_fee(u64::MAX), 0);
+    }
+
+    #[test]
+    fn format_amount() {
+        let crd = metadata(2, "CRD");
+        assert_eq!(crd.format_amount(1250), "12.50 CRD");
+        assert_eq!(crd.format_amount(5), "0.05 CRD");
+        assert_eq!(crd.format_amount(0), "0.00 CRD");
+        assert_eq!(metadata(0, "").format_amount(42), "42");
+        assert_eq!(
+            metadata(Metadata::MAX_DECIMALS, "").format_amount(u64::MAX),
+            "1.8446744073709551615"
+        );
+    }
+
+    #[test]
+    fn parse_amount() {
+        let crd = metadata(2, "CRD");
+        assert_eq!(crd.parse_amount("12.5"), Some(1250));
+        assert_eq!(crd.parse_amount("12"), Some(1200));
+        assert_eq!(crd.parse_amount("12."), Some(1200));
+        assert_eq!(crd.parse_amount(".05"), Some(5));
+        assert_eq!(crd.parse_amount("184467440737095516.15"), Some(u64::MAX));
+        let whole = metadata(0, "");
+        assert_eq!(whole.parse_amount("5"), Some(5));
+        assert_eq!(whole.parse_amount("5."), Some(5));
+    }
+
+    #[test]
+    fn parse_amount_rejects() {
+        let crd = metadata(2, "CRD");
+        for text in [
+            "",
+            ".",
+            "1.234",
+            "-1",
+            "+1",
+            "1e3",
+            " 1",
+            "1.2.3",
+            "12.50 CRD",
+            "184467440737095516.16",
+        ] {
+            assert_eq!(crd.parse_amount(text), None, "{:?}", text);
+        }
+        assert_eq!(metadata(0, "").parse_amount("5.0"), None);
+    }
+
+    #[test]
+    fn amount_round_trip() {
+        let crd = metadata(6, "");
+        for amount in [0, 1, 999_999, 1_000_000, 123_456_789, u64::MAX] {
+            assert_eq!(crd.parse_amount(&crd.format_amount(amount)), Some(amount));
+        }
+    }
+}